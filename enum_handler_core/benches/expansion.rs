@@ -0,0 +1,80 @@
+//! Tracks the expansion time of the macro for an enum with 1000 variants.
+//!
+//! ```bash
+//! cargo bench -p enum_handler_core --bench expansion
+//! ```
+
+use std::time::{Duration, Instant};
+
+use enum_handler_core::enum_handler_core;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+const VARIANTS: usize = 1000;
+const ITERATIONS: u32 = 10;
+
+/// Unit, tuple and struct variants with `String` fields, which are passed as `&str`.
+fn large_enum(attributes: TokenStream) -> TokenStream {
+    let variants = (0..VARIANTS).map(|i| {
+        let name = format_ident!("Variant{}", i);
+        match i % 3 {
+            0 => quote! { #name },
+            1 => quote! { #name(u32, String) },
+            _ => quote! { #name { id: u64, name: String, values: Vec<i32> } },
+        }
+    });
+    quote! {
+        #[derive(EnumHandler)]
+        #attributes
+        pub enum LargeEvent {
+            #(#variants),*
+        }
+    }
+}
+
+fn bench(
+    name: &str,
+    attributes: TokenStream,
+) {
+    let input = large_enum(attributes);
+    // the first expansion is not measured
+    enum_handler_core(input.clone()).unwrap();
+
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let output = enum_handler_core(input.clone()).unwrap();
+        total += start.elapsed();
+        drop(output);
+    }
+    println!(
+        "{:<24} {:>10.2?} per expansion of {} variants",
+        name,
+        total / ITERATIONS,
+        VARIANTS
+    );
+}
+
+fn main() {
+    bench("sync", quote! {});
+    bench(
+        "sync with defaults",
+        quote! { #[enum_handler(default_implementation = true, return_type = "i32", default_return_value = "0")] },
+    );
+    bench(
+        "async with mock",
+        quote! { #[enum_handler(is_async = true, mock_name = "LargeMock")] },
+    );
+    bench(
+        "boxed with mock",
+        quote! { #[enum_handler(is_async = true, async_style = "boxed", mock_name = "LargeMock")] },
+    );
+    bench(
+        "both flavours",
+        quote! { #[enum_handler(flavours = "both", pass_args_by_ref = true, error_type = "String")] },
+    );
+    bench(
+        "helpers",
+        quote! { #[enum_handler(accessors = true, kind = "LargeKind", metadata = true)] },
+    );
+}
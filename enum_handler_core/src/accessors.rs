@@ -0,0 +1,223 @@
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, Visibility};
+
+use crate::model::{Enum, EnumVariant, EnumVariantType};
+
+impl Enum {
+    /// `is_x()`, `as_x()`, `into_x()` for every variant and `variant_name()` (`accessors = true`).
+    pub fn generate_accessors(&self) -> TokenStream {
+        let visibility = &self
+            .config
+            .visibility;
+        let enum_name = &self.name;
+
+        let accessors = self
+            .variants
+            .iter()
+            .map(|v| v.generate_accessors(visibility))
+            .collect::<Vec<_>>();
+        let variant_name_arms = self
+            .variants
+            .iter()
+            .map(|v| {
+                let pattern = v.generate_wildcard_pattern();
+                let name = v
+                    .name
+                    .to_string();
+                quote! { #pattern => #name, }
+            })
+            .collect::<Vec<_>>();
+
+        quote! {
+            impl #enum_name {
+                #(#accessors)*
+
+                /// The name of the variant.
+                #visibility fn variant_name(&self) -> &'static str {
+                    match self {
+                        #(#variant_name_arms)*
+                    }
+                }
+            }
+        }
+    }
+
+    /// A fieldless enum with the variants of the enum (`kind = "..."`), and `kind()` to get it from the enum.
+    pub fn generate_kind(
+        &self,
+        kind_name: &Ident,
+    ) -> TokenStream {
+        let visibility = &self
+            .config
+            .visibility;
+        let enum_name = &self.name;
+        let kind_name_string = kind_name.to_string();
+        let crate_path = &self
+            .config
+            .crate_path;
+
+        let variant_names = self
+            .variants
+            .iter()
+            .map(|v| &v.name)
+            .collect::<Vec<_>>();
+        let variant_name_strings = variant_names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        let variant_count = variant_names.len();
+        let kind_arms = self
+            .variants
+            .iter()
+            .map(|v| {
+                let pattern = v.generate_wildcard_pattern();
+                let name = &v.name;
+                quote! { #pattern => #kind_name::#name, }
+            })
+            .collect::<Vec<_>>();
+
+        let doc = format!(" The variants of [`{}`] without their fields.", enum_name);
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #visibility enum #kind_name {
+                #(#variant_names,)*
+            }
+
+            impl #kind_name {
+                /// All variants in declaration order.
+                #visibility const ALL: [#kind_name; #variant_count] = [#(#kind_name::#variant_names),*];
+
+                /// The name of the variant.
+                #visibility fn name(&self) -> &'static str {
+                    match self {
+                        #(#kind_name::#variant_names => #variant_name_strings,)*
+                    }
+                }
+            }
+
+            impl ::core::fmt::Display for #kind_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(self.name())
+                }
+            }
+
+            impl ::core::str::FromStr for #kind_name {
+                type Err = #crate_path::ParseKindError;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    match s {
+                        #(#variant_name_strings => ::core::result::Result::Ok(#kind_name::#variant_names),)*
+                        _ => ::core::result::Result::Err(#crate_path::ParseKindError { kind: #kind_name_string }),
+                    }
+                }
+            }
+
+            impl ::core::convert::From<&#enum_name> for #kind_name {
+                fn from(e: &#enum_name) -> Self {
+                    e.kind()
+                }
+            }
+
+            impl #enum_name {
+                /// The variant without its fields.
+                #visibility fn kind(&self) -> #kind_name {
+                    match self {
+                        #(#kind_arms)*
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl EnumVariant {
+    fn generate_accessors(
+        &self,
+        visibility: &Visibility,
+    ) -> TokenStream {
+        let variant_name = &self.name;
+        let snake_name = self
+            .name
+            .to_string()
+            .to_snake_case();
+        let is_name = format_ident!("is_{}", snake_name);
+        let as_name = format_ident!("as_{}", snake_name);
+        let into_name = format_ident!("into_{}", snake_name);
+        let pattern = self.generate_wildcard_pattern();
+
+        let is_accessor = quote! {
+            #visibility fn #is_name(&self) -> bool {
+                ::core::matches!(self, #pattern)
+            }
+        };
+        if self
+            .fields
+            .is_empty()
+        {
+            return is_accessor;
+        }
+
+        let parameters = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| self.parameter_name(name, i))
+            .collect::<Vec<_>>();
+        let types = self
+            .fields
+            .iter()
+            .map(|(_, ty)| ty)
+            .collect::<Vec<_>>();
+        let pattern = match self.variant_type {
+            EnumVariantType::Struct => quote! { Self::#variant_name { #(#parameters),* } },
+            _ => quote! { Self::#variant_name( #(#parameters),* ) },
+        };
+        let (ref_type, owned_type, value) = if self.is_single_field() {
+            (
+                quote! { &#(#types)* },
+                quote! { #(#types)* },
+                quote! { #(#parameters)* },
+            )
+        } else {
+            (
+                quote! { (#(&#types),*) },
+                quote! { (#(#types),*) },
+                quote! { (#(#parameters),*) },
+            )
+        };
+
+        quote! {
+            #is_accessor
+
+            #[allow(unreachable_patterns)]
+            #visibility fn #as_name(&self) -> ::core::option::Option<#ref_type> {
+                match self {
+                    #pattern => ::core::option::Option::Some(#value),
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #[allow(unreachable_patterns)]
+            #visibility fn #into_name(self) -> ::core::option::Option<#owned_type> {
+                match self {
+                    #pattern => ::core::option::Option::Some(#value),
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    }
+
+    /// `Self::Variant`, `Self::Variant(..)` or `Self::Variant { .. }`, matching the variant regardless of its fields.
+    fn generate_wildcard_pattern(&self) -> TokenStream {
+        let variant_name = &self.name;
+        match self.variant_type {
+            EnumVariantType::Unit => quote! { Self::#variant_name },
+            EnumVariantType::Tuple => quote! { Self::#variant_name(..) },
+            EnumVariantType::Struct => quote! { Self::#variant_name { .. } },
+            EnumVariantType::Unknown => quote! { _ },
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{enum_handler_args::EnumHandlerArgs, generator::boxed_future, model::Enum, Result};
+
+impl Enum {
+    /// Adapters between the sync and the async trait if both flavours are generated:
+    /// - every sync handler implements the async trait with ready futures
+    /// - the blocking adapter implements the sync trait for an async handler
+    ///
+    /// `self` is the enum of the sync trait, `async_enum` the enum of the async trait.
+    pub fn generate_flavour_adapters(
+        &self,
+        sync_args: &EnumHandlerArgs,
+        async_enum: &Enum,
+        async_args: &EnumHandlerArgs,
+    ) -> Result<TokenStream> {
+        let visibility = &self
+            .config
+            .visibility;
+        let sync_trait_name = &self
+            .config
+            .trait_name;
+        let async_trait_name = &async_enum
+            .config
+            .trait_name;
+        let blocking_adapter_name = sync_args.get_blocking_adapter_name(&self.name);
+
+        let async_trait = async_args.get_async_trait_attribute();
+        let sync_bound = if !async_args.is_async_send() {
+            quote! {}
+        } else if async_args.use_async_trait_macro() {
+            quote! {+ Sync}
+        } else if async_args.is_boxed_async() {
+            quote! {+ Send + Sync}
+        } else {
+            quote! {}
+        };
+        // the default methods of `async_trait` with `Send` futures require `Self: Sync`
+        let blocking_bound = if async_args.use_async_trait_macro() && async_args.is_async_send() {
+            quote! {+ ::core::marker::Sync}
+        } else {
+            quote! {}
+        };
+
+        let ready_handlers = self
+            .handler_variants()
+            .map(|v| {
+                let handler_name = &v.handler_name;
+                let return_type = v.get_handler_return_type(async_args);
+                let mut params = v.generate_parameters(async_args);
+                let mut arguments = v.generate_arguments(async_args);
+                arguments.insert(0, quote! {self});
+                if async_args.is_boxed_async() {
+                    params.insert(0, quote! {&'a self});
+                    let boxed = &self
+                        .config
+                        .box_path;
+                    let boxed_future = boxed_future(async_args, boxed, return_type);
+                    return Ok(quote! {
+                        fn #handler_name<'a>(#(#params),*) -> #boxed_future {
+                            let output = #sync_trait_name::#handler_name(#(#arguments),*);
+                            #boxed::pin(async move { output })
+                        }
+                    });
+                }
+                params.insert(0, quote! {&self});
+                Ok(quote! {
+                    async fn #handler_name(#(#params),*) -> #return_type {
+                        #sync_trait_name::#handler_name(#(#arguments),*)
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let crate_path = &self
+            .config
+            .crate_path;
+        let blocking_handlers = self
+            .handler_variants()
+            .map(|v| {
+                let handler_name = &v.handler_name;
+                let return_type = v.get_handler_return_type(sync_args);
+                let mut params = v.generate_parameters(sync_args);
+                params.insert(0, quote! {&self});
+                let mut arguments = v.generate_arguments(sync_args);
+                arguments.insert(0, quote! {&self.0});
+                Ok(quote! {
+                    fn #handler_name(#(#params),*) -> #return_type {
+                        #crate_path::block_on(#async_trait_name::#handler_name(#(#arguments),*))
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let blocking_adapter_doc = format!(
+            " Implements [`{}`] for the [`{}`] `T` by running every handler to completion on the current thread.",
+            sync_trait_name, async_trait_name
+        );
+
+        let (ready_output, blocking_output) = if sync_args.is_associated_output() {
+            (
+                quote! { type Output = <T as #sync_trait_name>::Output; },
+                quote! { type Output = <T as #async_trait_name>::Output; },
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
+
+        Ok(quote! {
+            #async_trait
+            impl<T: #sync_trait_name #sync_bound + ?Sized> #async_trait_name for T {
+                #ready_output
+                #(#ready_handlers)*
+            }
+
+            #[doc = #blocking_adapter_doc]
+            #visibility struct #blocking_adapter_name<T>(pub T);
+
+            impl<T: #async_trait_name #blocking_bound> #sync_trait_name for #blocking_adapter_name<T> {
+                #blocking_output
+                #(#blocking_handlers)*
+            }
+        })
+    }
+}
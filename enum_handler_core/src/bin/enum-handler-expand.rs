@@ -0,0 +1,160 @@
+//! Prints the code generated for every `#[derive(EnumHandler)]` and `#[enum_handler(...)]` enum of a Rust file.
+//!
+//! ```bash
+//! cargo run -p enum_handler_core --bin enum-handler-expand -- [--enum Name]... [--json] <file.rs | ->
+//! ```
+
+use std::{
+    env, fs,
+    io::{self, Read},
+    process,
+};
+
+use enum_handler_core::{find_enums, format_tokens, Generator};
+use serde_json::json;
+use syn::ItemEnum;
+
+const USAGE: &str = "Usage: enum-handler-expand [--enum Name]... [--json] <file.rs | ->";
+
+struct Options {
+    path: String,
+    enum_names: Vec<String>,
+    json: bool,
+}
+
+struct Expansion {
+    enum_name: String,
+    result: Result<String, String>,
+}
+
+fn main() {
+    let options = match parse_options(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let source = match read_source(&options.path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Cannot read {}: {}", options.path, e);
+            process::exit(2);
+        }
+    };
+    let file = match syn::parse_file(&source) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("Cannot parse {}: {}", options.path, e);
+            process::exit(2);
+        }
+    };
+
+    let expansions = find_enums(&file)
+        .into_iter()
+        .filter(|item_enum| {
+            options
+                .enum_names
+                .is_empty()
+                || options
+                    .enum_names
+                    .contains(
+                        &item_enum
+                            .ident
+                            .to_string(),
+                    )
+        })
+        .map(expand)
+        .collect::<Vec<_>>();
+
+    if options.json {
+        print_json(&expansions);
+    } else {
+        print_code(&expansions);
+    }
+    if expansions
+        .iter()
+        .any(|expansion| {
+            expansion
+                .result
+                .is_err()
+        })
+    {
+        process::exit(1);
+    }
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut path = None;
+    let mut enum_names = Vec::new();
+    let mut json = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--enum" => enum_names.push(
+                args.next()
+                    .ok_or("--enum needs the name of an enum")?,
+            ),
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if path.is_some() => return Err(format!("Unexpected argument {}", arg)),
+            _ => path = Some(arg),
+        }
+    }
+    Ok(Options {
+        path: path.ok_or("Missing file")?,
+        enum_names,
+        json,
+    })
+}
+
+fn read_source(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut source = String::new();
+        io::stdin().read_to_string(&mut source)?;
+        return Ok(source);
+    }
+    fs::read_to_string(path)
+}
+
+fn expand(item_enum: ItemEnum) -> Expansion {
+    let enum_name = item_enum
+        .ident
+        .to_string();
+    let result = Generator::new()
+        .item_enum(item_enum)
+        .generate();
+    Expansion {
+        enum_name,
+        result: result
+            .map(|tokens| format_tokens(&tokens))
+            .map_err(|e| e.to_string()),
+    }
+}
+
+fn print_code(expansions: &[Expansion]) {
+    for expansion in expansions {
+        match &expansion.result {
+            Ok(code) => println!("// {}\n{}", expansion.enum_name, code),
+            Err(e) => eprintln!("// {}: {}", expansion.enum_name, e),
+        }
+    }
+}
+
+fn print_json(expansions: &[Expansion]) {
+    let output = expansions
+        .iter()
+        .map(|expansion| match &expansion.result {
+            Ok(code) => json!({ "enum": expansion.enum_name, "code": code }),
+            Err(e) => json!({ "enum": expansion.enum_name, "error": e }),
+        })
+        .collect::<Vec<_>>();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&output).unwrap_or_default()
+    );
+}
@@ -0,0 +1,211 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, Path, Type};
+
+use crate::{
+    config::Config,
+    enum_handler_args::EnumHandlerArgs,
+    generator::boxed_future,
+    model::{Enum, EnumVariant},
+};
+
+impl Enum {
+    /// Implements the trait for `&T`, `&mut T`, `Box<T>`, `Arc<T>` and `Rc<T>` of every implementor `T` by forwarding
+    /// the handler methods, and for `Option<T>`, which returns the default value for `None`.
+    ///
+    /// `Rc<T>` is left out if the futures must be `Send`. `Option<T>` is left out if there is no default value:
+    /// without `default_return_value` for a common return type other than `()`.
+    pub fn generate_blanket_impls(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> TokenStream {
+        let trait_name = &self
+            .config
+            .trait_name;
+        let crate_path = &self
+            .config
+            .crate_path;
+        let boxed = &self
+            .config
+            .box_path;
+
+        let async_trait = args.get_async_trait_attribute();
+        // the futures of the `async_trait` macro borrow `self` across `.await`, `&Arc<T>` is `Send` if `T` is `Send + Sync`
+        let (sync_bound, arc_bound) = if args.use_async_trait_macro() && args.is_async_send() {
+            (
+                quote! {+ ::core::marker::Sync},
+                quote! {+ ::core::marker::Send + ::core::marker::Sync},
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
+        let output = if args.is_associated_output() {
+            quote! { type Output = <T as #trait_name>::Output; }
+        } else {
+            quote! {}
+        };
+
+        let forward_handlers = self
+            .handler_variants()
+            .map(|v| v.generate_forward_handler(args, trait_name, boxed))
+            .collect::<Vec<_>>();
+
+        let mut pointers = vec![quote! { &T }, quote! { &mut T }, quote! { #boxed<T> }];
+        // `Rc` is neither `Send` nor `Sync`
+        let send = args.is_async_send() && (args.use_async_trait_macro() || args.is_boxed_async());
+        if !send {
+            pointers.push(quote! { #crate_path::__private::Rc<T> });
+        }
+        let mut output_tokens = pointers
+            .iter()
+            .map(|pointer| {
+                quote! {
+                    #async_trait
+                    impl<T: #trait_name #sync_bound + ?::core::marker::Sized> #trait_name for #pointer {
+                        #output
+                        #(#forward_handlers)*
+                    }
+                }
+            })
+            .collect::<TokenStream>();
+
+        output_tokens.extend(quote! {
+            #[cfg(target_has_atomic = "ptr")]
+            #async_trait
+            impl<T: #trait_name #arc_bound + ?::core::marker::Sized> #trait_name for #crate_path::__private::Arc<T> {
+                #output
+                #(#forward_handlers)*
+            }
+        });
+
+        let is_unit_return_type = matches!(&self.config.return_type, Type::Tuple(tuple) if tuple.elems.is_empty());
+        if !args.is_associated_output() && !args.has_default_return_value() && !is_unit_return_type {
+            return output_tokens;
+        }
+        let option_handlers = self
+            .handler_variants()
+            .map(|v| v.generate_option_handler(args, &self.config, trait_name))
+            .collect::<Vec<_>>();
+        let where_clause = if args.is_associated_output() {
+            quote! { where <T as #trait_name>::Output: ::core::default::Default }
+        } else {
+            quote! {}
+        };
+        output_tokens.extend(quote! {
+            #async_trait
+            impl<T: #trait_name #sync_bound> #trait_name for ::core::option::Option<T> #where_clause {
+                #output
+                #(#option_handlers)*
+            }
+        });
+        output_tokens
+    }
+}
+
+impl EnumVariant {
+    /// The handler method of a pointer, calls the handler method of the pointee.
+    fn generate_forward_handler(
+        &self,
+        args: &EnumHandlerArgs,
+        trait_name: &Ident,
+        boxed: &Path,
+    ) -> TokenStream {
+        let handler_name = &self.handler_name;
+        let return_type = self.get_handler_return_type(args);
+        let mut params = self.generate_parameters(args);
+        let mut arguments = self.generate_arguments(args);
+        arguments.insert(0, quote! {&**self});
+
+        if self.is_boxed_async(args) {
+            params.insert(0, quote! {&'a self});
+            let boxed_future = boxed_future(args, boxed, return_type);
+            return quote! {
+                fn #handler_name<'a>(#(#params),*) -> #boxed_future {
+                    #trait_name::#handler_name(#(#arguments),*)
+                }
+            };
+        }
+        params.insert(0, quote! {&self});
+        if self.is_async(args) {
+            return quote! {
+                async fn #handler_name(#(#params),*) -> #return_type {
+                    #trait_name::#handler_name(#(#arguments),*).await
+                }
+            };
+        }
+        quote! {
+            fn #handler_name(#(#params),*) -> #return_type {
+                #trait_name::#handler_name(#(#arguments),*)
+            }
+        }
+    }
+
+    /// The handler method of `Option<T>`, calls the handler method of `Some` and returns a default value for `None`.
+    fn generate_option_handler(
+        &self,
+        args: &EnumHandlerArgs,
+        config: &Config,
+        trait_name: &Ident,
+    ) -> TokenStream {
+        let handler_name = &self.handler_name;
+        let return_type = self.get_handler_return_type(args);
+        let mut params = self.generate_parameters(args);
+        let mut arguments = self.generate_arguments(args);
+        let none_value = self.generate_none_value(args, config);
+
+        // the binding of the handler must not shadow a field
+        let mut inner = String::from("inner");
+        while self.has_field_named(&inner) {
+            inner.push('_');
+        }
+        let inner = format_ident!("{}", inner);
+        arguments.insert(0, quote! {#inner});
+
+        if self.is_boxed_async(args) {
+            params.insert(0, quote! {&'a self});
+            let boxed = &config.box_path;
+            let boxed_future = boxed_future(args, boxed, return_type);
+            return quote! {
+                fn #handler_name<'a>(#(#params),*) -> #boxed_future {
+                    match self {
+                        ::core::option::Option::Some(#inner) => #trait_name::#handler_name(#(#arguments),*),
+                        ::core::option::Option::None => #boxed::pin(async move { #none_value }),
+                    }
+                }
+            };
+        }
+        params.insert(0, quote! {&self});
+        let (async_fn, await_fn) = if self.is_async(args) { (quote! {async}, quote! {.await}) } else { (quote! {}, quote! {}) };
+        quote! {
+            #async_fn fn #handler_name(#(#params),*) -> #return_type {
+                match self {
+                    ::core::option::Option::Some(#inner) => #trait_name::#handler_name(#(#arguments),*) #await_fn,
+                    ::core::option::Option::None => #none_value,
+                }
+            }
+        }
+    }
+
+    /// The result of the handler method of `None`: the `default_return_value` or `()`, in `Ok` with `error_type`.
+    /// Variants with their own return types and the associated `Output` return `Default::default()`.
+    fn generate_none_value(
+        &self,
+        args: &EnumHandlerArgs,
+        config: &Config,
+    ) -> TokenStream {
+        if self.has_own_return_type || args.is_associated_output() {
+            return config
+                .own_return_value
+                .clone();
+        }
+        if config
+            .return_value
+            .is_empty()
+        {
+            return quote! {()};
+        }
+        config
+            .return_value
+            .clone()
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{Attribute, DeriveInput, Item, ItemEnum, Meta};
+
+use crate::{enum_handler_args::EnumHandlerArgs, enum_handler_args::HELPER_ATTRIBUTE, Error, Result};
+
+const DEFAULT_FILE_NAME: &str = "enum_handler.rs";
+
+enum Source {
+    File(PathBuf),
+    Enum(ItemEnum),
+}
+
+/// Generates the handler code outside of the macros, eg. in a build script for enums generated from IDL files.
+///
+/// ```no_run
+/// use enum_handler_core::{enum_handler_args::EnumHandlerArgs, Generator};
+///
+/// let out_dir = std::env::var("OUT_DIR").unwrap();
+/// Generator::new()
+///     .args(EnumHandlerArgs {
+///         is_async: true,
+///         ..Default::default()
+///     })
+///     .enum_source("idl/events.rs")
+///     .write_to(out_dir)
+///     .unwrap();
+/// ```
+///
+/// The generated file is included with `include!(concat!(env!("OUT_DIR"), "/enum_handler.rs"));`.
+///
+/// - Without [`Generator::args`], the enums of the sources with `#[derive(EnumHandler)]` or the attribute macro are
+///   generated with their own `#[enum_handler(...)]` attributes, like the macros would.
+/// - With [`Generator::args`], every enum of the sources is generated with the given options and its own attributes.
+/// - Enums of the attribute macro are written together with the generated code, like the attribute macro does. The
+///   other enums only if [`Generator::include_enums`] is set.
+#[derive(Default)]
+pub struct Generator {
+    args_list: Vec<EnumHandlerArgs>,
+    sources: Vec<Source>,
+    include_enums: bool,
+    file_name: Option<String>,
+}
+
+impl Generator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a trait with the given options, may be called more than once.
+    pub fn args(
+        mut self,
+        args: EnumHandlerArgs,
+    ) -> Self {
+        self.args_list
+            .push(args);
+        self
+    }
+
+    /// Adds the enums of a Rust file, including the ones in inline modules.
+    pub fn enum_source(
+        mut self,
+        path: impl AsRef<Path>,
+    ) -> Self {
+        self.sources
+            .push(Source::File(
+                path.as_ref()
+                    .to_path_buf(),
+            ));
+        self
+    }
+
+    /// Adds an enum, it is generated even without the derive or the attribute macro.
+    pub fn item_enum(
+        mut self,
+        item_enum: ItemEnum,
+    ) -> Self {
+        self.sources
+            .push(Source::Enum(item_enum));
+        self
+    }
+
+    /// Writes the enums, without the `EnumHandler` derive and the `#[enum_handler]` attributes, together with the
+    /// generated code.
+    pub fn include_enums(
+        mut self,
+        include_enums: bool,
+    ) -> Self {
+        self.include_enums = include_enums;
+        self
+    }
+
+    /// The name of the file written by [`Generator::write_to`], `enum_handler.rs` by default.
+    pub fn file_name(
+        mut self,
+        file_name: impl Into<String>,
+    ) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// The generated code of all enums.
+    pub fn generate(&self) -> Result<TokenStream> {
+        let mut output = TokenStream::new();
+        for item_enum in self.collect_enums()? {
+            output.extend(self.generate_enum(item_enum)?);
+        }
+        Ok(output)
+    }
+
+    /// Writes the formatted code into the directory and returns the path of the file.
+    pub fn write_to(
+        &self,
+        out_dir: impl AsRef<Path>,
+    ) -> Result<PathBuf> {
+        let output = self.generate()?;
+        let path = out_dir
+            .as_ref()
+            .join(
+                self.file_name
+                    .as_deref()
+                    .unwrap_or(DEFAULT_FILE_NAME),
+            );
+        fs::write(&path, crate::debug::format_tokens(&output)).map_err(|_| {
+            Error::CannotWriteFile(
+                path.display()
+                    .to_string(),
+            )
+        })?;
+        Ok(path)
+    }
+
+    fn collect_enums(&self) -> Result<Vec<ItemEnum>> {
+        let mut enums = Vec::new();
+        for source in &self.sources {
+            match source {
+                Source::File(path) => {
+                    let source = fs::read_to_string(path).map_err(|_| {
+                        Error::CannotReadFile(
+                            path.display()
+                                .to_string(),
+                        )
+                    })?;
+                    let file = syn::parse_file(&source)?;
+                    if self
+                        .args_list
+                        .is_empty()
+                    {
+                        enums.extend(find_enums(&file));
+                    } else {
+                        collect_all_enums(&file.items, &mut enums);
+                    }
+                }
+                Source::Enum(item_enum) => enums.push(item_enum.clone()),
+            }
+        }
+        Ok(enums)
+    }
+
+    fn generate_enum(
+        &self,
+        mut item_enum: ItemEnum,
+    ) -> Result<TokenStream> {
+        let mut args_list = self
+            .args_list
+            .clone();
+        let attribute_macro = find_attribute_macro(&item_enum.attrs).map(|index| {
+            item_enum
+                .attrs
+                .remove(index)
+        });
+        if let Some(attr) = &attribute_macro {
+            let attr = match &attr.meta {
+                Meta::List(list) => list
+                    .tokens
+                    .clone(),
+                _ => TokenStream::new(),
+            };
+            args_list.push(EnumHandlerArgs::from_attribute(attr)?);
+        }
+        let mut derive_input = syn::parse2::<DeriveInput>(item_enum.to_token_stream())?;
+        // the default trait of an enum without attributes is only generated if no other options are given
+        if args_list.is_empty()
+            || derive_input
+                .attrs
+                .iter()
+                .any(|attr| {
+                    attr.path()
+                        .is_ident(HELPER_ATTRIBUTE)
+                })
+        {
+            args_list.extend(EnumHandlerArgs::list_from_derive_input(&derive_input)?);
+        }
+
+        let traits = crate::Traits::new(&derive_input, args_list)?;
+        let generated = crate::generate(&derive_input, &traits)?;
+        if attribute_macro.is_none() && !self.include_enums {
+            return Ok(generated);
+        }
+        crate::strip_helper_attributes(&mut derive_input);
+        strip_derive(&mut derive_input.attrs);
+        let mut output = derive_input.to_token_stream();
+        output.extend(generated);
+        Ok(output)
+    }
+}
+
+/// The enums using the derive or the attribute macro, including the ones in inline modules.
+pub fn find_enums(file: &syn::File) -> Vec<ItemEnum> {
+    let mut enums = Vec::new();
+    collect_all_enums(&file.items, &mut enums);
+    enums.retain(|item_enum| is_derived(&item_enum.attrs) || find_attribute_macro(&item_enum.attrs).is_some());
+    enums
+}
+
+fn collect_all_enums(
+    items: &[Item],
+    enums: &mut Vec<ItemEnum>,
+) {
+    for item in items {
+        match item {
+            Item::Enum(item_enum) => enums.push(item_enum.clone()),
+            Item::Mod(item_mod) => {
+                if let Some((_, items)) = &item_mod.content {
+                    collect_all_enums(items, enums);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_enum_handler_derive(path: &syn::Path) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "EnumHandler")
+}
+
+/// `#[derive(EnumHandler)]` or `#[derive(enum_handler::EnumHandler)]`
+fn is_derived(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| {
+            attr.path()
+                .is_ident("derive")
+        })
+        .any(|attr| {
+            let mut derived = false;
+            let _ = attr.parse_nested_meta(|meta| {
+                derived |= is_enum_handler_derive(&meta.path);
+                Ok(())
+            });
+            derived
+        })
+}
+
+/// Without the derive, the first `#[enum_handler(...)]` or `#[enum_handler::enum_handler(...)]` attribute is the attribute macro.
+fn find_attribute_macro(attrs: &[Attribute]) -> Option<usize> {
+    if is_derived(attrs) {
+        return None;
+    }
+    attrs
+        .iter()
+        .position(|attr| {
+            attr.path()
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == HELPER_ATTRIBUTE)
+        })
+}
+
+/// Removes `EnumHandler` from the derives, the code of the derive is already generated.
+fn strip_derive(attrs: &mut Vec<Attribute>) {
+    for attr in attrs.iter_mut() {
+        if !attr
+            .path()
+            .is_ident("derive")
+        {
+            continue;
+        }
+        let mut paths = Vec::new();
+        let _ = attr.parse_nested_meta(|meta| {
+            paths.push(meta.path);
+            Ok(())
+        });
+        if paths
+            .iter()
+            .any(is_enum_handler_derive)
+        {
+            paths.retain(|path| !is_enum_handler_derive(path));
+            *attr = syn::parse_quote!(#[derive(#(#paths),*)]);
+        }
+    }
+    attrs.retain(|attr| match &attr.meta {
+        Meta::List(list) => {
+            !(list
+                .path
+                .is_ident("derive")
+                && list
+                    .tokens
+                    .is_empty())
+        }
+        _ => true,
+    });
+}
@@ -0,0 +1,48 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Path;
+
+use crate::model::Enum;
+
+impl Enum {
+    /// Calls the `macro_rules!` macro of `callback = ...` with the variants of the trait:
+    ///
+    /// ```text
+    /// my_macro! { enum = Name, trait = Trait, variants = [ (Variant, on_variant, [field: Type, ...]), ... ] }
+    /// ```
+    ///
+    /// Tuple fields are named like the parameters of the handler methods (`arg`, `arg0`, `arg1`, ...).
+    pub fn generate_callback(
+        &self,
+        callback: &Path,
+    ) -> TokenStream {
+        let enum_name = &self.name;
+        let trait_name = &self
+            .config
+            .trait_name;
+        let variants = self
+            .variants
+            .iter()
+            .map(|v| {
+                let variant_name = &v.name;
+                let handler_name = &v.handler_name;
+                let fields = v
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (name, ty))| {
+                        let name = v.parameter_name(name, i);
+                        quote! { #name: #ty }
+                    });
+                quote! { (#variant_name, #handler_name, [#(#fields),*]) }
+            });
+
+        quote! {
+            #callback! {
+                enum = #enum_name,
+                trait = #trait_name,
+                variants = [#(#variants),*]
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Ident, Path, Type, Visibility};
+
+use crate::{enum_handler_args::EnumHandlerArgs, Error, Result};
+
+/// The options of one trait, parsed and validated once for all variants.
+///
+/// The trait, the mock and the helpers of large enums would otherwise parse the same options for every variant.
+pub struct Config {
+    /// The name of the trait, see [`Config::get_trait_name`] for the `Mut` trait of `lock_impls`.
+    pub trait_name: Ident,
+    /// The name of the `Mut` trait of `lock_impls`.
+    pub mut_trait_name: Ident,
+    /// The common handler method, eg. `on`.
+    pub handler_name: Ident,
+    /// The common handler method that wraps failures with the failed variant, eg. `try_on`.
+    pub try_handler_name: Ident,
+    /// The enum returned by the common handler method if variants have their own return types.
+    pub output_name: Ident,
+    /// The error returned by the `try_` handler method, shared by the `Mut` trait.
+    pub error_name: Ident,
+    pub visibility: Visibility,
+    /// The common return type, `Self::Output` with `associated_output`.
+    pub return_type: Type,
+    /// The common return type of the mock, `mock_output` with `associated_output`.
+    pub mock_return_type: Type,
+    /// Given by `error_type`, the handler methods return `Result<R, E>`.
+    pub error_type: Option<Type>,
+    /// The body of the default implementations and of `None` in the `Option` blanket impl, given by `default_return_value`.
+    pub return_value: TokenStream,
+    /// The body of the default implementations of variants with their own return types, which can't return the
+    /// common `default_return_value`: `Default::default()`.
+    pub own_return_value: TokenStream,
+    /// The path of the `enum_handler` crate in the generated code.
+    pub crate_path: Path,
+    /// The `Box` of the boxed futures.
+    pub box_path: Path,
+}
+
+impl Config {
+    pub fn new(
+        args: &EnumHandlerArgs,
+        enum_name: &Ident,
+        enum_visibility: &Visibility,
+    ) -> Result<Self> {
+        args.validate_async_style()?;
+        args.validate_associated_output()?;
+        if args.is_pass_event() && args.is_move_arguments() {
+            return Err(Error::PassEventRequiresArgsByRef);
+        }
+
+        let return_type = args.get_return_type()?;
+        let mock_return_type = if args.is_associated_output() { args.get_mock_output()? } else { return_type.clone() };
+        let error_type = if args.is_fallible() { Some(args.get_error_type()?) } else { None };
+        let return_value = args.get_return_value()?;
+        let own_return_value = if error_type.is_some() {
+            quote! {::core::result::Result::Ok(::core::default::Default::default())}
+        } else {
+            quote! {::core::default::Default::default()}
+        };
+
+        Ok(Config {
+            trait_name: args.get_trait_name(enum_name),
+            mut_trait_name: args
+                .clone_for_mut_trait()
+                .get_trait_name(enum_name),
+            handler_name: args.get_handler_name(),
+            try_handler_name: args.get_try_handler_name(),
+            output_name: args.get_output_name(enum_name),
+            error_name: args.get_error_name(enum_name),
+            visibility: args.visibility(enum_visibility)?,
+            return_type,
+            mock_return_type,
+            error_type,
+            return_value,
+            own_return_value,
+            crate_path: args.get_crate_path(),
+            box_path: args.get_box_path(),
+        })
+    }
+
+    /// The name of the trait generated with `args`, which are the options of this trait or of its `Mut` trait.
+    pub fn get_trait_name(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> &Ident {
+        if args.is_mutable_receiver() {
+            &self.mut_trait_name
+        } else {
+            &self.trait_name
+        }
+    }
+
+    /// `Result<R, E>` with `error_type`, the return type itself otherwise.
+    pub fn get_fallible_return_type(
+        &self,
+        return_type: Type,
+    ) -> Type {
+        match &self.error_type {
+            Some(error_type) => parse_quote! { ::core::result::Result<#return_type, #error_type> },
+            None => return_type,
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use proc_macro2::TokenStream;
+use syn::Ident;
+
+use crate::{Error, Result};
+
+static DEBUG_FILE_ENV: &str = "ENUM_HANDLER_DEBUG";
+static FORMAT_DEBUG_FILE_ENV: &str = "ENUM_HANDLER_DEBUG_FORMAT";
+static DEBUG_FILTER_ENV: &str = "ENUM_HANDLER_DEBUG_FILTER";
+static DEBUG_MAX_SIZE_ENV: &str = "ENUM_HANDLER_DEBUG_MAX_SIZE";
+static DEBUG_EDITION_ENV: &str = "ENUM_HANDLER_DEBUG_EDITION";
+
+const FORMAT_RUSTFMT: &str = "rustfmt";
+const DEFAULT_EDITION: &str = "2021";
+
+static DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 128;
+
+/// Writes the input and the generated code of the macro if `ENUM_HANDLER_DEBUG` is set.
+///
+/// - If `ENUM_HANDLER_DEBUG` is a directory (an existing one, or the path ends with `/`), every enum is written to its
+///   own file `{crate}.{Enum}.rs`, so parallel builds don't write to the same file.
+/// - Otherwise each block is appended to the file with a single write. The file is started again if it is larger than
+///   `ENUM_HANDLER_DEBUG_MAX_SIZE` bytes (default 128 KiB, `0` for no limit). The file is not locked, so parallel builds
+///   that reach the limit at the same time can remove each other's blocks, only the directory mode is safe for them.
+/// - `ENUM_HANDLER_DEBUG_FILTER` selects enums by a comma separated list of names or globs (`*` and `?`).
+pub fn write_debug_file(
+    enum_name: &Ident,
+    input: &TokenStream,
+    output: &TokenStream,
+) -> Result<()> {
+    let debug_path = env::var(DEBUG_FILE_ENV).unwrap_or_default();
+    if debug_path.is_empty() {
+        return Ok(());
+    }
+    let enum_name = enum_name.to_string();
+    let filter = env::var(DEBUG_FILTER_ENV).unwrap_or_default();
+    if !matches_filter(&filter, &enum_name) {
+        return Ok(());
+    }
+
+    let crate_name = crate_name();
+    let block = format_block(&crate_name, &enum_name, input, output);
+
+    if debug_path.ends_with('/') || Path::new(&debug_path).is_dir() {
+        let path = PathBuf::from(&debug_path).join(format!("{}.{}.rs", crate_name, enum_name));
+        fs::create_dir_all(&debug_path).map_err(|_| cannot_write(&debug_path))?;
+        return fs::write(&path, block).map_err(|_| {
+            cannot_write(
+                &path
+                    .display()
+                    .to_string(),
+            )
+        });
+    }
+
+    let max_size = env::var(DEBUG_MAX_SIZE_ENV)
+        .ok()
+        .and_then(|max_size| {
+            max_size
+                .parse::<u64>()
+                .ok()
+        })
+        .unwrap_or(DEFAULT_MAX_FILE_SIZE);
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&debug_path)
+        .map_err(|_| cannot_write(&debug_path))?;
+    // the size of the opened file, it is emptied right before the write to keep the window for parallel builds small
+    let truncate = max_size > 0
+        && file
+            .metadata()
+            .is_ok_and(|metadata| metadata.len() > max_size);
+    let block = if truncate {
+        format!(
+            "// The previous output was removed, the file was larger than {} bytes ({}).\n{}",
+            max_size, DEBUG_MAX_SIZE_ENV, block
+        )
+    } else {
+        block
+    };
+
+    if truncate {
+        file.set_len(0)
+            .map_err(|_| cannot_write(&debug_path))?;
+    }
+    // a single write of the whole block, so blocks of parallel builds are not interleaved
+    file.write_all(block.as_bytes())
+        .map_err(|_| cannot_write(&debug_path))
+}
+
+/// The crate of the expanded enum, which prefixes the written files so enums with the same name don't overwrite each other.
+pub(crate) fn crate_name() -> String {
+    env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn cannot_write(path: &str) -> Error {
+    Error::CannotWriteDebugFile(path.to_string(), DEBUG_FILE_ENV.to_string())
+}
+
+/// How the code in the debug file is formatted, given by `ENUM_HANDLER_DEBUG_FORMAT`.
+enum DebugFormat {
+    /// The code as one line, `ENUM_HANDLER_DEBUG_FORMAT` is not set.
+    None,
+    /// In-process with `prettyplease`, the default if `ENUM_HANDLER_DEBUG_FORMAT` is set.
+    PrettyPlease,
+    /// `ENUM_HANDLER_DEBUG_FORMAT=rustfmt` with the `rustfmt.toml` of the crate and the edition from
+    /// `ENUM_HANDLER_DEBUG_EDITION`, `prettyplease` is used if `rustfmt` fails.
+    Rustfmt(String),
+}
+
+impl DebugFormat {
+    fn from_env() -> Self {
+        match env::var(FORMAT_DEBUG_FILE_ENV)
+            .unwrap_or_default()
+            .as_str()
+        {
+            "" => DebugFormat::None,
+            FORMAT_RUSTFMT => DebugFormat::Rustfmt(env::var(DEBUG_EDITION_ENV).unwrap_or_else(|_| DEFAULT_EDITION.to_string())),
+            _ => DebugFormat::PrettyPlease,
+        }
+    }
+
+    fn format(
+        &self,
+        tokens: &TokenStream,
+    ) -> String {
+        match self {
+            DebugFormat::None => tokens.to_string(),
+            DebugFormat::PrettyPlease => format_tokens(tokens),
+            DebugFormat::Rustfmt(edition) => rustfmt(&tokens.to_string(), edition).unwrap_or_else(|| format_tokens(tokens)),
+        }
+    }
+}
+
+fn format_block(
+    crate_name: &str,
+    enum_name: &str,
+    input: &TokenStream,
+    output: &TokenStream,
+) -> String {
+    let format = DebugFormat::from_env();
+    format!(
+        r#"
+// ************************************************************
+// crate: {}, enum: {}, generated at: {}
+// ------------------------------------------------------------
+// Given input:
+{}
+// ------------------------------------------------------------
+// Generated output:
+{}
+// ************************************************************
+
+"#,
+        crate_name,
+        enum_name,
+        utc_timestamp(SystemTime::now()),
+        format.format(input),
+        format.format(output)
+    )
+}
+
+/// Formats the code as a file with `prettyplease`, the code is returned as one line if it is no valid file.
+///
+/// Shared with the `enum-handler-expand` binary, not a public API.
+#[doc(hidden)]
+pub fn format_tokens(tokens: &TokenStream) -> String {
+    match syn::parse2::<syn::File>(tokens.clone()) {
+        Ok(file) => prettyplease::unparse(&file),
+        Err(_) => tokens.to_string(),
+    }
+}
+
+/// Formats the code with `rustfmt`, `None` if it is not installed or fails.
+fn rustfmt(
+    code: &str,
+    edition: &str,
+) -> Option<String> {
+    let mut command = Command::new("rustfmt");
+    command
+        .arg(format!("--edition={}", edition))
+        .arg("--emit=stdout")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    // the rustfmt.toml of the crate that uses the macro
+    if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+        command
+            .arg("--config-path")
+            .arg(manifest_dir);
+    }
+    let mut child = command
+        .spawn()
+        .ok()?;
+    child
+        .stdin
+        .take()?
+        .write_all(code.as_bytes())
+        .ok()?;
+    let output = child
+        .wait_with_output()
+        .ok()?;
+    if !output
+        .status
+        .success()
+    {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// Whether the enum is selected by the comma separated names or globs, an empty filter selects all enums.
+pub(crate) fn matches_filter(
+    filter: &str,
+    enum_name: &str,
+) -> bool {
+    if filter
+        .trim()
+        .is_empty()
+    {
+        return true;
+    }
+    filter
+        .split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .any(|pattern| matches_glob(pattern.as_bytes(), enum_name.as_bytes()))
+}
+
+/// `*` matches any characters, `?` matches a single character.
+fn matches_glob(
+    pattern: &[u8],
+    name: &[u8],
+) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => matches_glob(&pattern[1..], name) || (!name.is_empty() && matches_glob(pattern, &name[1..])),
+        (Some(b'?'), Some(_)) => matches_glob(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => matches_glob(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SSZ`
+pub(crate) fn utc_timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let (days, seconds_of_day) = (seconds / 86_400, seconds % 86_400);
+
+    // civil date from days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60
+    )
+}
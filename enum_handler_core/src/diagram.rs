@@ -0,0 +1,264 @@
+use std::{env, fmt::Write, fs, path::Path};
+
+use syn::DeriveInput;
+
+use crate::{debug, enum_handler_args::EnumHandlerArgs, meta::type_to_string, model::Enum, Error, Result};
+
+static DIAGRAM_DIR_ENV: &str = "ENUM_HANDLER_DIAGRAM_DIR";
+
+/// Writes `{crate}.{Enum}.mmd` (Mermaid) and `{crate}.{Enum}.dot` (Graphviz) into the directory given by `ENUM_HANDLER_DIAGRAM_DIR`.
+///
+/// `enums` are the enums of the traits, resolved with the options in `flavour_args_list`.
+pub fn write_diagram_files(
+    derive_input: &DeriveInput,
+    flavour_args_list: &[EnumHandlerArgs],
+    enums: &[Enum],
+) -> Result<()> {
+    let diagram_dir = env::var(DIAGRAM_DIR_ENV).unwrap_or_default();
+    if diagram_dir.is_empty() {
+        return Ok(());
+    }
+    write_diagram_files_to(
+        Path::new(&diagram_dir),
+        derive_input,
+        flavour_args_list,
+        enums,
+    )
+}
+
+pub(crate) fn write_diagram_files_to(
+    diagram_dir: &Path,
+    derive_input: &DeriveInput,
+    flavour_args_list: &[EnumHandlerArgs],
+    enums: &[Enum],
+) -> Result<()> {
+    let diagram = Diagram::new(flavour_args_list, enums);
+    let enum_name = &derive_input.ident;
+    let crate_name = debug::crate_name();
+
+    fs::create_dir_all(diagram_dir).map_err(|_| cannot_write(diagram_dir))?;
+    for (file_name, content) in [
+        (
+            format!("{}.{}.mmd", crate_name, enum_name),
+            diagram.to_mermaid(),
+        ),
+        (
+            format!("{}.{}.dot", crate_name, enum_name),
+            diagram.to_dot(&enum_name.to_string()),
+        ),
+    ] {
+        let path = diagram_dir.join(file_name);
+        fs::write(&path, content).map_err(|_| cannot_write(&path))?;
+    }
+    Ok(())
+}
+
+fn cannot_write(path: &Path) -> Error {
+    Error::CannotWriteDiagramFile(
+        path.display()
+            .to_string(),
+        DIAGRAM_DIR_ENV.to_string(),
+    )
+}
+
+enum NodeShape {
+    Enum,
+    Variant,
+    Trait,
+    Method,
+}
+
+struct Node {
+    id: String,
+    label: String,
+    shape: NodeShape,
+}
+
+enum EdgeStyle {
+    /// The enum has the variant, the trait has the method.
+    Contains,
+    /// The variant is handled by the method.
+    HandledBy,
+    /// The variant can be followed by the other variant (`next`).
+    Transition,
+}
+
+struct Edge {
+    from: String,
+    to: String,
+    style: EdgeStyle,
+}
+
+/// enum -> variants -> handler methods <- traits, and the transitions between the variants
+struct Diagram {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+impl Diagram {
+    fn new(
+        flavour_args_list: &[EnumHandlerArgs],
+        enums: &[Enum],
+    ) -> Self {
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let Some(e) = enums.first() else {
+            return Diagram {
+                nodes,
+                edges,
+            };
+        };
+
+        let enum_id = e
+            .name
+            .to_string();
+        nodes.push(Node {
+            id: enum_id.clone(),
+            label: format!("enum {}", e.name),
+            shape: NodeShape::Enum,
+        });
+        for v in &e.variants {
+            let types = v
+                .fields
+                .iter()
+                .map(|(_, ty)| type_to_string(ty))
+                .collect::<Vec<_>>();
+            let label = if types.is_empty() {
+                v.name
+                    .to_string()
+            } else {
+                format!("{}({})", v.name, types.join(", "))
+            };
+            let variant_id = format!("{}_{}", enum_id, v.name);
+            nodes.push(Node {
+                id: variant_id.clone(),
+                label,
+                shape: NodeShape::Variant,
+            });
+            edges.push(Edge {
+                from: enum_id.clone(),
+                to: variant_id,
+                style: EdgeStyle::Contains,
+            });
+        }
+        for v in &e.variants {
+            for next in &v.next {
+                edges.push(Edge {
+                    from: format!("{}_{}", enum_id, v.name),
+                    to: format!("{}_{}", enum_id, next),
+                    style: EdgeStyle::Transition,
+                });
+            }
+        }
+
+        for (args, e) in flavour_args_list
+            .iter()
+            .zip(enums)
+        {
+            let trait_name = &e
+                .config
+                .trait_name;
+            let trait_id = trait_name.to_string();
+            nodes.push(Node {
+                id: trait_id.clone(),
+                label: format!("trait {}", trait_name),
+                shape: NodeShape::Trait,
+            });
+            for v in &e.variants {
+                let handler_name = &v.handler_name;
+                let parameters = v
+                    .generate_parameters(args)
+                    .iter()
+                    .map(type_to_string)
+                    .collect::<Vec<_>>();
+                let async_fn = if v.is_async(args) { "async " } else { "" };
+                let method_id = format!("{}_{}", trait_id, handler_name);
+                nodes.push(Node {
+                    id: method_id.clone(),
+                    label: format!(
+                        "{}{}({}) -> {}",
+                        async_fn,
+                        handler_name,
+                        parameters.join(", "),
+                        type_to_string(v.get_handler_return_type(args))
+                    ),
+                    shape: NodeShape::Method,
+                });
+                edges.push(Edge {
+                    from: trait_id.clone(),
+                    to: method_id.clone(),
+                    style: EdgeStyle::Contains,
+                });
+                edges.push(Edge {
+                    from: format!("{}_{}", enum_id, v.name),
+                    to: method_id,
+                    style: EdgeStyle::HandledBy,
+                });
+            }
+        }
+        Diagram {
+            nodes,
+            edges,
+        }
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut output = String::from("flowchart LR\n");
+        for node in &self.nodes {
+            let label = node
+                .label
+                .replace('"', "#quot;")
+                .replace('<', "#lt;")
+                .replace('>', "#gt;");
+            let _ = match node.shape {
+                NodeShape::Enum => writeln!(output, "    {}[[\"{}\"]]", node.id, label),
+                NodeShape::Variant => writeln!(output, "    {}([\"{}\"])", node.id, label),
+                NodeShape::Trait => writeln!(output, "    {}{{{{\"{}\"}}}}", node.id, label),
+                NodeShape::Method => writeln!(output, "    {}[\"{}\"]", node.id, label),
+            };
+        }
+        for edge in &self.edges {
+            let arrow = match edge.style {
+                EdgeStyle::Contains => "-->",
+                EdgeStyle::HandledBy => "-.->",
+                EdgeStyle::Transition => "==>",
+            };
+            let _ = writeln!(output, "    {} {} {}", edge.from, arrow, edge.to);
+        }
+        output
+    }
+
+    fn to_dot(
+        &self,
+        name: &str,
+    ) -> String {
+        let mut output = format!("digraph {} {{\n    rankdir=LR;\n", name);
+        for node in &self.nodes {
+            let label = node
+                .label
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"");
+            let shape = match node.shape {
+                NodeShape::Enum => "box3d",
+                NodeShape::Variant => "ellipse",
+                NodeShape::Trait => "component",
+                NodeShape::Method => "box",
+            };
+            let _ = writeln!(
+                output,
+                "    {} [shape={}, label=\"{}\"];",
+                node.id, shape, label
+            );
+        }
+        for edge in &self.edges {
+            let style = match edge.style {
+                EdgeStyle::Contains => "",
+                EdgeStyle::HandledBy => " [style=dashed]",
+                EdgeStyle::Transition => " [style=bold]",
+            };
+            let _ = writeln!(output, "    {} -> {}{};", edge.from, edge.to, style);
+        }
+        output.push_str("}\n");
+        output
+    }
+}
@@ -1,31 +1,188 @@
-use darling::*;
+use darling::{ast::NestedMeta, FromMeta};
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
-use syn::{parse_str, Expr, Ident, Type, Visibility};
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse::Parser, parse_quote, parse_str, punctuated::Punctuated, DeriveInput, Expr, Ident, LitStr, Path, Token, Type, TypeParamBound, Variant, Visibility};
 
 use crate::{Error, Result};
 
+pub const HELPER_ATTRIBUTE: &str = "enum_handler";
+
 const DEFAULT_TRAIT_SUFFIX: &str = "Handler";
 const DEFAULT_HANDLER_NAME: &str = "on";
 const DEFAULT_RETURN_TYPE: &str = "()";
 
-#[derive(Debug, Clone, Default, FromDeriveInput)]
-#[darling(default, attributes(enum_handler))]
+const FLAVOURS_SYNC: &str = "sync";
+const FLAVOURS_ASYNC: &str = "async";
+const FLAVOURS_BOTH: &str = "both";
+const OUTPUT_ENUM_SUFFIX: &str = "Output";
+const ASSOCIATED_OUTPUT_TYPE: &str = "Self::Output";
+const ASYNC_STYLE_BOXED: &str = "boxed";
+const ASYNC_FLAVOUR_PREFIX: &str = "Async";
+const BLOCKING_ADAPTER_PREFIX: &str = "Blocking";
+const ERROR_SUFFIX: &str = "Error";
+const TRY_HANDLER_PREFIX: &str = "try";
+const MUT_TRAIT_SUFFIX: &str = "Mut";
+
+/// The options of one `#[enum_handler(...)]` attribute, each one describes a generated trait.
+///
+/// The options are described in the README. Outside of the macros, eg. with the [`crate::Generator`] in a build
+/// script, the options can be given directly:
+///
+/// ```
+/// use enum_handler_core::enum_handler_args::EnumHandlerArgs;
+///
+/// let args = EnumHandlerArgs {
+///     trait_name: "CounterHandler".to_string(),
+///     is_async: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default)]
 pub struct EnumHandlerArgs {
-    trait_suffix: String,
-    trait_name: String,
-    handler_name: String,
-    return_type: String,
-    default_return_value: String,
-    is_async: bool,
-    default_implementation: bool,
-    visibility: String,
-    no_async_trait_macro: bool,
-    mock_name: String,
-    pass_args_by_ref: bool,
+    pub trait_suffix: String,
+    pub trait_name: String,
+    pub handler_name: String,
+    pub return_type: String,
+    pub default_return_value: String,
+    pub is_async: bool,
+    pub default_implementation: bool,
+    pub visibility: String,
+    pub no_async_trait_macro: bool,
+    pub mock_name: String,
+    pub pass_args_by_ref: bool,
+    pub pass_event: bool,
+    pub remote: String,
+    pub non_exhaustive: bool,
+    pub flavours: String,
+    pub async_style: String,
+    pub async_send: Option<bool>,
+    pub associated_output: bool,
+    pub output_bounds: String,
+    pub mock_output: String,
+    pub output_name: String,
+    pub error_type: String,
+    pub accessors: bool,
+    pub kind: String,
+    pub metadata: bool,
+    pub callback: Option<Path>,
+    pub blanket_impls: bool,
+    pub lock_impls: bool,
+    pub async_mutex: Option<Path>,
+    #[darling(rename = "crate")]
+    pub crate_path: Option<Path>,
+    /// Set by the generator for the derived traits and mocks, this is no option.
+    #[darling(skip)]
+    pub internal: InternalArgs,
+}
+
+/// The settings of the traits and mocks derived from the options, eg. the sync and the async trait of `flavours = "both"`.
+#[derive(Debug, Clone, Default)]
+pub struct InternalArgs {
+    is_mock: bool,
+    /// Set for the traits of `flavours = "both"`, which are either completely sync or async.
+    ignore_variant_asyncness: bool,
+    /// The asyncness of variants without `#[enum_handler(async)]` or `#[enum_handler(sync)]`, if it differs from the trait.
+    default_variant_async: Option<bool>,
+    /// Set for the `Mut` trait of `lock_impls`, its methods take `&mut self`.
+    mutable_receiver: bool,
+}
+
+/// The options of the `#[enum_handler(...)]` attribute on a variant.
+#[derive(Debug, Clone, Default)]
+pub struct EnumHandlerVariantArgs {
+    pub asyncness: Option<bool>,
+    /// `returns = "Type"`, the return type of this variant's handler method.
+    pub returns: Option<Type>,
+    /// `next = "A, B"`, the variants that can follow this variant, drawn as transitions in the diagrams.
+    pub next: Vec<Ident>,
+}
+
+impl EnumHandlerVariantArgs {
+    pub fn from_variant(variant: &Variant) -> Result<Self> {
+        let mut variant_args = Self::default();
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| {
+                attr.path()
+                    .is_ident(HELPER_ATTRIBUTE)
+            })
+        {
+            attr.parse_nested_meta(|meta| {
+                let asyncness = if meta
+                    .path
+                    .is_ident("async")
+                {
+                    true
+                } else if meta
+                    .path
+                    .is_ident("sync")
+                {
+                    false
+                } else if meta
+                    .path
+                    .is_ident("returns")
+                {
+                    let returns = meta
+                        .value()?
+                        .parse::<LitStr>()?;
+                    variant_args.returns = Some(returns.parse::<Type>()?);
+                    return Ok(());
+                } else if meta
+                    .path
+                    .is_ident("next")
+                {
+                    let next = meta
+                        .value()?
+                        .parse::<LitStr>()?;
+                    variant_args
+                        .next
+                        .extend(next.parse_with(Punctuated::<Ident, Token![,]>::parse_terminated)?);
+                    return Ok(());
+                } else {
+                    return Err(meta.error("unknown enum_handler variant option, expected `async`, `sync`, `returns` or `next`"));
+                };
+                if variant_args
+                    .asyncness
+                    .is_some_and(|a| a != asyncness)
+                {
+                    return Err(meta.error("a variant can't be both `async` and `sync`"));
+                }
+                variant_args.asyncness = Some(asyncness);
+                Ok(())
+            })?;
+        }
+        Ok(variant_args)
+    }
 }
 
 impl EnumHandlerArgs {
+    /// Parses every `#[enum_handler(...)]` attribute of the enum, each one describes a separate trait.
+    ///
+    /// Without any attribute a single trait with the default options is generated.
+    pub fn list_from_derive_input(derive_input: &DeriveInput) -> Result<Vec<Self>> {
+        let args_list = derive_input
+            .attrs
+            .iter()
+            .filter(|attr| {
+                attr.path()
+                    .is_ident(HELPER_ATTRIBUTE)
+            })
+            .map(|attr| Self::from_meta(&attr.meta).map_err(Error::from))
+            .collect::<Result<Vec<_>>>()?;
+
+        if args_list.is_empty() {
+            return Ok(vec![Self::default()]);
+        }
+        Ok(args_list)
+    }
+
+    /// Parses the options given to the `#[enum_handler(...)]` attribute macro.
+    pub fn from_attribute(attr: TokenStream) -> Result<Self> {
+        Self::from_list(&NestedMeta::parse_meta_list(attr)?).map_err(Error::from)
+    }
+
     pub fn get_trait_suffix(&self) -> String {
         if self
             .trait_suffix
@@ -37,9 +194,21 @@ impl EnumHandlerArgs {
             .clone()
     }
 
+    /// The name of the trait, with the suffix `Mut` for the `Mut` trait of `lock_impls`.
     pub fn get_trait_name(
         &self,
         enum_name: &Ident,
+    ) -> Ident {
+        let trait_name = self.get_base_trait_name(enum_name);
+        if self.is_mutable_receiver() {
+            return format_ident!("{}{}", trait_name, MUT_TRAIT_SUFFIX);
+        }
+        trait_name
+    }
+
+    fn get_base_trait_name(
+        &self,
+        enum_name: &Ident,
     ) -> Ident {
         if self
             .trait_name
@@ -76,6 +245,15 @@ impl EnumHandlerArgs {
     }
 
     pub fn get_return_type(&self) -> Result<Type> {
+        if self.is_associated_output() {
+            if self
+                .internal
+                .is_mock
+            {
+                return self.get_mock_output();
+            }
+            return parse_str::<Type>(ASSOCIATED_OUTPUT_TYPE).map_err(Error::from);
+        }
         if self
             .return_type
             .is_empty()
@@ -86,14 +264,197 @@ impl EnumHandlerArgs {
     }
 
     pub fn get_return_value(&self) -> Result<TokenStream> {
-        if self
+        let return_value = if self.is_associated_output() && self.is_default_output() {
+            quote! {::core::default::Default::default()}
+        } else if self
             .default_return_value
             .is_empty()
             || self.default_return_value == "()"
         {
-            return Ok(quote! {});
+            quote! {}
+        } else {
+            parse_str::<Expr>(&self.default_return_value)?.to_token_stream()
+        };
+        if self.is_fallible() {
+            if return_value.is_empty() {
+                return Ok(quote! {::core::result::Result::Ok(())});
+            }
+            return Ok(quote! {::core::result::Result::Ok(#return_value)});
+        }
+        Ok(return_value)
+    }
+
+    /// Every handler method returns `Result<R, E>` with the `error_type` `E`.
+    pub fn is_fallible(&self) -> bool {
+        !self
+            .error_type
+            .is_empty()
+    }
+
+    pub fn get_error_type(&self) -> Result<Type> {
+        parse_str::<Type>(&self.error_type).map_err(Error::from)
+    }
+
+    /// The name of the error returned by the `try_` handler method, eg. `CounterEventHandlerError`.
+    pub fn get_error_name(
+        &self,
+        enum_name: &Ident,
+    ) -> Ident {
+        // the `Mut` trait of `lock_impls` shares the error of its trait
+        format_ident!("{}{}", self.get_base_trait_name(enum_name), ERROR_SUFFIX)
+    }
+
+    /// The common handler method that wraps failures with the failed variant, eg. `try_on`.
+    pub fn get_try_handler_name(&self) -> Ident {
+        format_ident!("{}_{}", TRY_HANDLER_PREFIX, self.get_handler_name())
+    }
+
+    /// The trait declares `type Output;` which is returned by every handler method instead of `return_type`.
+    pub fn is_associated_output(&self) -> bool {
+        self.associated_output
+    }
+
+    /// A `default_return_value` is given.
+    pub fn has_default_return_value(&self) -> bool {
+        !self
+            .default_return_value
+            .is_empty()
+    }
+
+    /// The default implementations return `Default::default()`.
+    fn is_default_output(&self) -> bool {
+        self.default_implementation
+            && self
+                .default_return_value
+                .is_empty()
+    }
+
+    pub fn validate_associated_output(&self) -> Result<()> {
+        if self.is_associated_output()
+            && !self
+                .return_type
+                .is_empty()
+        {
+            return Err(Error::AssociatedOutputWithReturnType);
         }
-        Ok(parse_str::<Expr>(&self.default_return_value)?.to_token_stream())
+        Ok(())
+    }
+
+    /// The bounds of the associated `Output` type, `Default` is required for the default implementations.
+    pub fn get_output_bounds(&self) -> Result<Vec<TypeParamBound>> {
+        let mut bounds = if self
+            .output_bounds
+            .is_empty()
+        {
+            Vec::new()
+        } else {
+            Punctuated::<TypeParamBound, Token![+]>::parse_terminated
+                .parse_str(&self.output_bounds)?
+                .into_iter()
+                .collect()
+        };
+        if self.is_default_output() {
+            bounds.push(parse_str::<TypeParamBound>("::core::default::Default")?);
+        }
+        Ok(bounds)
+    }
+
+    /// The name of the enum returned by the common handler method if variants have their own return types.
+    pub fn get_output_name(
+        &self,
+        enum_name: &Ident,
+    ) -> Ident {
+        if self
+            .output_name
+            .is_empty()
+        {
+            format_ident!("{}{}", enum_name, OUTPUT_ENUM_SUFFIX)
+        } else {
+            format_ident!("{}", self.output_name)
+        }
+    }
+
+    /// `is_x()`, `as_x()`, `into_x()` and `variant_name()` are generated for the enum.
+    pub fn is_accessors(&self) -> bool {
+        self.accessors
+    }
+
+    /// The name of the generated fieldless kind enum, if any.
+    pub fn get_kind_name(&self) -> Result<Option<Ident>> {
+        if self
+            .kind
+            .is_empty()
+        {
+            return Ok(None);
+        }
+        Ok(Some(parse_str::<Ident>(&self.kind)?))
+    }
+
+    /// The handler methods of the trait are listed by `EnumHandlerMeta::HANDLER_METHODS`.
+    pub fn is_metadata(&self) -> bool {
+        self.metadata
+    }
+
+    /// The trait is implemented for `&T`, `&mut T`, `Box<T>`, `Arc<T>`, `Rc<T>` and `Option<T>` of its implementors.
+    pub fn is_blanket_impls(&self) -> bool {
+        self.blanket_impls
+    }
+
+    /// The trait is implemented for `Mutex<T>` and `RwLock<T>`, or for the `async_mutex`, of the implementors of the `Mut` trait.
+    pub fn is_lock_impls(&self) -> bool {
+        self.lock_impls
+    }
+
+    /// The mutex of the lock impls of async traits, eg. `tokio::sync::Mutex`.
+    pub fn get_async_mutex(&self) -> Option<&Path> {
+        self.async_mutex
+            .as_ref()
+    }
+
+    /// The options of the `Mut` trait of `lock_impls`.
+    pub fn is_mutable_receiver(&self) -> bool {
+        self.internal
+            .mutable_receiver
+    }
+
+    /// `&self`, or `&mut self` for the `Mut` trait of `lock_impls`.
+    pub fn get_receiver(&self) -> TokenStream {
+        if self.is_mutable_receiver() {
+            quote! {&mut self}
+        } else {
+            quote! {&self}
+        }
+    }
+
+    /// The receiver of the methods returning boxed futures, which borrow `self` for `'a`.
+    pub fn get_boxed_receiver(&self) -> TokenStream {
+        if self.is_mutable_receiver() {
+            quote! {&'a mut self}
+        } else {
+            quote! {&'a self}
+        }
+    }
+
+    /// The `macro_rules!` macro that is called with the variants of the trait (`callback = my_crate::my_macro`).
+    pub fn get_callback(&self) -> Option<&Path> {
+        self.callback
+            .as_ref()
+    }
+
+    pub fn is_mock(&self) -> bool {
+        self.internal
+            .is_mock
+    }
+
+    /// The concrete `Output` type of the mock, `()` by default.
+    pub fn get_mock_output(&self) -> Result<Type> {
+        if self
+            .mock_output
+            .is_empty()
+        {
+            return parse_str::<Type>(DEFAULT_RETURN_TYPE).map_err(Error::from);
+        }
+        parse_str::<Type>(&self.mock_output).map_err(Error::from)
     }
 
     pub fn is_generate_mock(&self) -> bool {
@@ -114,21 +475,230 @@ impl EnumHandlerArgs {
         self.pass_args_by_ref
     }
 
+    pub fn is_pass_event(&self) -> bool {
+        self.pass_event
+    }
+
+    pub fn is_remote(&self) -> bool {
+        !self
+            .remote
+            .is_empty()
+    }
+
+    /// The dispatcher passes unknown variants of a `#[non_exhaustive]` enum to the `unknown` handler method.
+    pub fn is_non_exhaustive(&self) -> bool {
+        self.non_exhaustive
+    }
+
+    pub fn get_remote(&self) -> Result<Option<Path>> {
+        if !self.is_remote() {
+            return Ok(None);
+        }
+        Ok(Some(parse_str::<Path>(&self.remote)?))
+    }
+
     pub fn is_move_arguments(&self) -> bool {
         !self.is_pass_args_by_ref()
     }
 
     pub fn use_async_trait_macro(&self) -> bool {
-        self.is_async && !self.no_async_trait_macro
+        self.is_async() && !self.no_async_trait_macro && !self.is_boxed_async()
+    }
+
+    /// The futures of the handler methods must be `Send`, this is the default.
+    pub fn is_async_send(&self) -> bool {
+        self.async_send
+            .unwrap_or(true)
+    }
+
+    /// `#[async_trait::async_trait]`, or `#[async_trait::async_trait(?Send)]` if the futures don't need to be `Send`.
+    pub fn get_async_trait_attribute(&self) -> TokenStream {
+        let async_trait = self.get_async_trait_path();
+        if !self.use_async_trait_macro() {
+            quote! {}
+        } else if self.is_async_send() {
+            quote! {#[#async_trait::async_trait]}
+        } else {
+            quote! {#[#async_trait::async_trait(?Send)]}
+        }
+    }
+
+    /// The path of the `enum_handler` crate in the generated code, `::enum_handler` or `crate = "..."`.
+    pub fn get_crate_path(&self) -> Path {
+        self.crate_path
+            .clone()
+            .unwrap_or_else(|| parse_quote!(::enum_handler))
+    }
+
+    /// `Box` for the boxed futures, re-exported by `enum_handler` so it works in `no_std` crates with the feature `alloc`.
+    pub fn get_box_path(&self) -> Path {
+        let crate_path = self.get_crate_path();
+        parse_quote!(#crate_path::__private::Box)
+    }
+
+    /// `async_trait`, or the re-export of `enum_handler` with the feature `async-trait`.
+    pub fn get_async_trait_path(&self) -> Path {
+        if cfg!(feature = "async-trait") {
+            let crate_path = self.get_crate_path();
+            parse_quote!(#crate_path::__private::async_trait)
+        } else {
+            parse_quote!(async_trait)
+        }
+    }
+
+    /// `mockall`, or the re-export of `enum_handler` with the feature `mock`.
+    pub fn get_mockall_path(&self) -> Path {
+        if cfg!(feature = "mock") {
+            let crate_path = self.get_crate_path();
+            parse_quote!(#crate_path::__private::mockall)
+        } else {
+            parse_quote!(mockall)
+        }
+    }
+
+    /// The handler methods return `Pin<Box<dyn Future>>` instead of being `async fn`.
+    pub fn is_boxed_async(&self) -> bool {
+        self.is_async() && self.async_style == ASYNC_STYLE_BOXED
+    }
+
+    pub fn validate_async_style(&self) -> Result<()> {
+        match self
+            .async_style
+            .as_str()
+        {
+            "" | ASYNC_STYLE_BOXED => Ok(()),
+            _ => Err(Error::InvalidAsyncStyle(
+                self.async_style
+                    .clone(),
+            )),
+        }
     }
 
     pub fn is_async(&self) -> bool {
-        self.is_async
+        self.is_async || self.flavours == FLAVOURS_ASYNC
+    }
+
+    pub fn is_both_flavours(&self) -> Result<bool> {
+        match self
+            .flavours
+            .as_str()
+        {
+            "" | FLAVOURS_SYNC | FLAVOURS_ASYNC => Ok(false),
+            FLAVOURS_BOTH => Ok(true),
+            _ => Err(Error::InvalidFlavours(
+                self.flavours
+                    .clone(),
+            )),
+        }
+    }
+
+    /// Whether a variant is async, `asyncness` is given by `#[enum_handler(async)]` or `#[enum_handler(sync)]`.
+    pub fn is_variant_async(
+        &self,
+        asyncness: Option<bool>,
+    ) -> bool {
+        if self
+            .internal
+            .ignore_variant_asyncness
+        {
+            return self.is_async();
+        }
+        asyncness
+            .or(self
+                .internal
+                .default_variant_async)
+            .unwrap_or(self.is_async())
+    }
+
+    /// The options of a sync trait with async variants: the trait and its dispatcher become async,
+    /// the other variants stay sync.
+    pub fn clone_for_async_variants(&self) -> Self {
+        EnumHandlerArgs {
+            is_async: true,
+            internal: InternalArgs {
+                default_variant_async: Some(false),
+                ..self
+                    .internal
+                    .clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// The options of the sync trait if both flavours are generated.
+    pub fn clone_for_sync_flavour(&self) -> Self {
+        EnumHandlerArgs {
+            is_async: false,
+            flavours: FLAVOURS_SYNC.to_string(),
+            internal: InternalArgs {
+                ignore_variant_asyncness: true,
+                ..self
+                    .internal
+                    .clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// The options of the async trait if both flavours are generated, the mock is only generated for the sync trait.
+    pub fn clone_for_async_flavour(
+        &self,
+        enum_name: &Ident,
+    ) -> Self {
+        EnumHandlerArgs {
+            trait_name: format!("{}{}", ASYNC_FLAVOUR_PREFIX, self.get_trait_name(enum_name)),
+            is_async: true,
+            flavours: FLAVOURS_ASYNC.to_string(),
+            mock_name: String::new(),
+            // the blanket and lock impls of the sync trait implement the async trait through the adapter
+            blanket_impls: false,
+            lock_impls: false,
+            internal: InternalArgs {
+                ignore_variant_asyncness: true,
+                ..self
+                    .internal
+                    .clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    pub fn get_blocking_adapter_name(
+        &self,
+        enum_name: &Ident,
+    ) -> Ident {
+        format_ident!(
+            "{}{}",
+            BLOCKING_ADAPTER_PREFIX,
+            self.get_trait_name(enum_name)
+        )
+    }
+
+    /// The options of the `Mut` trait of `lock_impls`, the same trait with `&mut self` receivers.
+    pub fn clone_for_mut_trait(&self) -> Self {
+        EnumHandlerArgs {
+            mock_name: String::new(),
+            blanket_impls: false,
+            lock_impls: false,
+            internal: InternalArgs {
+                mutable_receiver: true,
+                ..self
+                    .internal
+                    .clone()
+            },
+            ..self.clone()
+        }
     }
 
     pub fn clone_for_mock(&self) -> Self {
         EnumHandlerArgs {
             default_implementation: false,
+            internal: InternalArgs {
+                is_mock: true,
+                ..self
+                    .internal
+                    .clone()
+            },
             ..self.clone()
         }
     }
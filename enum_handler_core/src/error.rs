@@ -12,6 +12,38 @@ pub enum Error {
     InvalidReturnType,
     #[error("Invalid return value")]
     InvalidReturnValue,
+    #[error("pass_event = true requires pass_args_by_ref = true")]
+    PassEventRequiresArgsByRef,
+    #[error("pass_event = true conflicts with the field `e` of variant {0}")]
+    PassEventFieldConflict(String),
+    #[error("The variant {0} has the unknown variant {1} in next")]
+    UnknownNextVariant(String, String),
+    #[error("non_exhaustive = true conflicts with the variant Unknown, please rename it")]
+    NonExhaustiveVariantConflict,
+    #[error("The trait {0} is generated more than once, please use distinct trait names")]
+    DuplicateTraitName(String),
+    #[error("The mock {0} is generated more than once, please use distinct mock names")]
+    DuplicateMockName(String),
+    #[error("Invalid flavours: {0}, expected \"sync\", \"async\" or \"both\"")]
+    InvalidFlavours(String),
+    #[error("Invalid async_style: {0}, expected \"boxed\"")]
+    InvalidAsyncStyle(String),
+    #[error("associated_output = true can't be combined with return_type")]
+    AssociatedOutputWithReturnType,
+    #[error("associated_output = true can't be combined with returns on variants")]
+    AssociatedOutputWithVariantReturns,
+    #[error("lock_impls = true requires async_mutex for async traits")]
+    LockImplsRequireAsyncMutex,
+    #[error("lock_impls = true can't be combined with sync and async variants in one trait")]
+    LockImplsWithMixedAsync,
+    #[error("Cannot write export file: {0}, please check the environment variable {1}")]
+    CannotWriteExportFile(String, String),
+    #[error("Cannot write diagram file: {0}, please check the environment variable {1}")]
+    CannotWriteDiagramFile(String, String),
+    #[error("Cannot read file: {0}")]
+    CannotReadFile(String),
+    #[error("Cannot write file: {0}")]
+    CannotWriteFile(String),
     #[error("Cannot write debug file: {0}, please check the environment variable {1}")]
     CannotWriteDebugFile(String, String),
 }
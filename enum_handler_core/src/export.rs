@@ -0,0 +1,528 @@
+use std::{env, fs, path::Path};
+
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use serde_json::{json, Map, Value};
+use syn::{parenthesized, parse::ParseStream, punctuated::Punctuated, Attribute, Data, DeriveInput, Expr, Field, GenericArgument, LitStr, PathArguments, Token, Type};
+
+use crate::{
+    debug,
+    enum_handler_args::EnumHandlerArgs,
+    meta::type_to_string,
+    model::{Enum, EnumVariant, EnumVariantType},
+    Error, Result,
+};
+
+static EXPORT_DIR_ENV: &str = "ENUM_HANDLER_EXPORT_DIR";
+static JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Writes `{crate}.{Enum}.json` with the handler interface and `{crate}.{Enum}.schema.json` with the JSON Schema of the
+/// serialized enum into the directory given by `ENUM_HANDLER_EXPORT_DIR`.
+///
+/// `enums` are the enums of the traits, resolved with the options in `flavour_args_list`.
+pub fn write_export_files(
+    derive_input: &DeriveInput,
+    flavour_args_list: &[EnumHandlerArgs],
+    enums: &[Enum],
+) -> Result<()> {
+    let export_dir = env::var(EXPORT_DIR_ENV).unwrap_or_default();
+    if export_dir.is_empty() {
+        return Ok(());
+    }
+    write_export_files_to(
+        Path::new(&export_dir),
+        derive_input,
+        flavour_args_list,
+        enums,
+    )
+}
+
+pub(crate) fn write_export_files_to(
+    export_dir: &Path,
+    derive_input: &DeriveInput,
+    flavour_args_list: &[EnumHandlerArgs],
+    enums: &[Enum],
+) -> Result<()> {
+    let Some(e) = enums.first() else {
+        return Ok(());
+    };
+
+    let interface = e.export_interface(flavour_args_list, enums);
+    let schema = e.export_schema(derive_input);
+
+    let crate_name = debug::crate_name();
+    fs::create_dir_all(export_dir).map_err(|_| cannot_write(export_dir))?;
+    for (file_name, value) in [(format!("{}.{}.json", crate_name, e.name), interface), (format!("{}.{}.schema.json", crate_name, e.name), schema)] {
+        let path = export_dir.join(file_name);
+        let content = serde_json::to_string_pretty(&value).map_err(|_| cannot_write(&path))?;
+        fs::write(&path, content).map_err(|_| cannot_write(&path))?;
+    }
+    Ok(())
+}
+
+fn cannot_write(path: &Path) -> Error {
+    Error::CannotWriteExportFile(
+        path.display()
+            .to_string(),
+        EXPORT_DIR_ENV.to_string(),
+    )
+}
+
+impl Enum {
+    /// The variants with their fields, and the handler methods of every generated trait.
+    fn export_interface(
+        &self,
+        flavour_args_list: &[EnumHandlerArgs],
+        enums: &[Enum],
+    ) -> Value {
+        let variants = self
+            .variants
+            .iter()
+            .map(|v| {
+                let fields = v
+                    .fields
+                    .iter()
+                    .map(|(name, ty)| {
+                        json!({
+                            "name": name.as_ref().map(|name| name.to_string()),
+                            "type": type_to_string(ty),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                json!({
+                    "name": v.name.to_string(),
+                    "kind": v.variant_type.as_str(),
+                    "fields": fields,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let traits = flavour_args_list
+            .iter()
+            .zip(enums)
+            .map(|(args, e)| e.export_trait(args))
+            .collect::<Vec<_>>();
+
+        json!({
+            "name": self.name.to_string(),
+            "variants": variants,
+            "traits": traits,
+        })
+    }
+
+    fn export_trait(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> Value {
+        let return_type = self
+            .config
+            .get_fallible_return_type(self.get_output_type());
+        let methods = self
+            .variants
+            .iter()
+            .map(|v| {
+                let parameters = v
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (name, ty))| {
+                        json!({
+                            "name": v.parameter_name(name, i).to_string(),
+                            "type": type_to_string(ty),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                json!({
+                    "variant": v.name.to_string(),
+                    "name": v.handler_name.to_string(),
+                    "parameters": parameters,
+                    "return_type": type_to_string(v.get_handler_return_type(args)),
+                    "is_async": v.is_async(args),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "name": self.config.trait_name.to_string(),
+            "handler_name": self.config.handler_name.to_string(),
+            "return_type": type_to_string(&return_type),
+            "is_async": args.is_async() || self.has_async_variant(args),
+            "methods": methods,
+        })
+    }
+
+    /// The JSON Schema of the enum serialized by serde, following the `#[serde(...)]` attributes of the enum,
+    /// its variants and fields.
+    fn export_schema(
+        &self,
+        derive_input: &DeriveInput,
+    ) -> Value {
+        let container = SerdeAttributes::from_attributes(&derive_input.attrs);
+        let Data::Enum(data_enum) = &derive_input.data else { unreachable!("only enums are exported") };
+        let variants = self
+            .variants
+            .iter()
+            .zip(&data_enum.variants)
+            .filter_map(|(v, variant)| {
+                let attributes = SerdeAttributes::from_attributes(&variant.attrs);
+                if attributes.skip {
+                    return None;
+                }
+                let fields = variant
+                    .fields
+                    .iter()
+                    .filter_map(|field| SerdeField::new(field, &attributes))
+                    .collect::<Vec<_>>();
+                let name = attributes
+                    .rename
+                    .unwrap_or_else(|| {
+                        rename(
+                            &v.name
+                                .to_string(),
+                            container
+                                .rename_all
+                                .as_deref(),
+                        )
+                    });
+                Some(v.export_schema(&name, &fields, &container))
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "$schema": JSON_SCHEMA_DIALECT,
+            "title": self.name.to_string(),
+            "oneOf": variants,
+        })
+    }
+}
+
+impl EnumVariant {
+    /// The variant in the representation of the enum:
+    /// - externally tagged (default): `"Variant"` for unit variants, `{ "Variant": content }` otherwise
+    /// - internally tagged (`tag`): `{ "tag": "Variant", ...fields }`
+    /// - adjacently tagged (`tag` and `content`): `{ "tag": "Variant", "content": content }`
+    /// - untagged: the content only
+    fn export_schema(
+        &self,
+        name: &str,
+        fields: &[SerdeField],
+        container: &SerdeAttributes,
+    ) -> Value {
+        let content = match self.variant_type {
+            EnumVariantType::Unit => None,
+            EnumVariantType::Unknown => unreachable!("the unknown variants are not exported"),
+            EnumVariantType::Tuple if fields.len() == 1 => Some(type_schema(fields[0].ty)),
+            EnumVariantType::Tuple => Some(tuple_schema(
+                fields
+                    .iter()
+                    .map(|field| field.ty),
+            )),
+            EnumVariantType::Struct => Some(object_schema(
+                fields
+                    .iter()
+                    .map(|field| {
+                        (
+                            field
+                                .name
+                                .clone(),
+                            type_schema(field.ty),
+                        )
+                    }),
+            )),
+        };
+
+        if container.untagged {
+            return content.unwrap_or_else(|| json!({ "type": "null" }));
+        }
+        let tag = match (&container.tag, &container.content) {
+            (Some(tag), Some(content_name)) => {
+                let properties = [(tag.clone(), json!({ "const": name }))]
+                    .into_iter()
+                    .chain(content.map(|content| (content_name.clone(), content)));
+                return object_schema(properties);
+            }
+            (Some(tag), None) => tag,
+            _ => {
+                return match content {
+                    Some(content) => object_schema([(name.to_string(), content)]),
+                    None => json!({ "const": name }),
+                };
+            }
+        };
+
+        let tag_schema = object_schema([(tag.clone(), json!({ "const": name }))]);
+        match (&self.variant_type, content) {
+            (EnumVariantType::Struct, Some(mut content)) => {
+                content["properties"][tag] = json!({ "const": name });
+                content["required"]
+                    .as_array_mut()
+                    .expect("the required properties of an object")
+                    .insert(0, json!(tag));
+                content
+            }
+            // serde merges the tag into the object of a newtype variant
+            (_, Some(content)) => json!({ "allOf": [tag_schema, content] }),
+            (_, None) => tag_schema,
+        }
+    }
+}
+
+/// The `#[serde(...)]` attributes of an enum, a variant or a field that change the serialized form.
+#[derive(Default)]
+struct SerdeAttributes {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    tag: Option<String>,
+    content: Option<String>,
+    untagged: bool,
+    skip: bool,
+}
+
+impl SerdeAttributes {
+    /// Other serde attributes are ignored, as is an attribute that can't be parsed.
+    fn from_attributes(attrs: &[Attribute]) -> Self {
+        let mut attributes = SerdeAttributes::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| {
+                attr.path()
+                    .is_ident("serde")
+            })
+        {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta
+                    .path
+                    .is_ident("rename")
+                {
+                    attributes.rename = serialize_name(meta.input)?.or(attributes
+                        .rename
+                        .take());
+                } else if meta
+                    .path
+                    .is_ident("rename_all")
+                {
+                    attributes.rename_all = serialize_name(meta.input)?.or(attributes
+                        .rename_all
+                        .take());
+                } else if meta
+                    .path
+                    .is_ident("tag")
+                {
+                    attributes.tag = Some(
+                        meta.value()?
+                            .parse::<LitStr>()?
+                            .value(),
+                    );
+                } else if meta
+                    .path
+                    .is_ident("content")
+                {
+                    attributes.content = Some(
+                        meta.value()?
+                            .parse::<LitStr>()?
+                            .value(),
+                    );
+                } else if meta
+                    .path
+                    .is_ident("untagged")
+                {
+                    attributes.untagged = true;
+                } else if meta
+                    .path
+                    .is_ident("skip")
+                    || meta
+                        .path
+                        .is_ident("skip_serializing")
+                {
+                    attributes.skip = true;
+                } else if meta
+                    .input
+                    .peek(Token![=])
+                {
+                    meta.value()?
+                        .parse::<Expr>()?;
+                } else if meta
+                    .input
+                    .peek(syn::token::Paren)
+                {
+                    let _content;
+                    parenthesized!(_content in meta.input);
+                }
+                Ok(())
+            });
+        }
+        attributes
+    }
+}
+
+/// `= "name"` or `(serialize = "name", deserialize = "...")`, the name used for serialization.
+fn serialize_name(input: ParseStream) -> syn::Result<Option<String>> {
+    if input.peek(Token![=]) {
+        input.parse::<Token![=]>()?;
+        return Ok(Some(
+            input
+                .parse::<LitStr>()?
+                .value(),
+        ));
+    }
+    let content;
+    parenthesized!(content in input);
+    let mut name = None;
+    for meta in Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated(&content)? {
+        if meta
+            .path
+            .is_ident("serialize")
+        {
+            if let Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(value),
+                ..
+            }) = meta.value
+            {
+                name = Some(value.value());
+            }
+        }
+    }
+    Ok(name)
+}
+
+/// A serialized field with its serde name, skipped fields are `None`.
+struct SerdeField<'a> {
+    name: String,
+    ty: &'a Type,
+}
+
+impl<'a> SerdeField<'a> {
+    fn new(
+        field: &'a Field,
+        variant: &SerdeAttributes,
+    ) -> Option<Self> {
+        let attributes = SerdeAttributes::from_attributes(&field.attrs);
+        if attributes.skip {
+            return None;
+        }
+        let name = match (attributes.rename, &field.ident) {
+            (Some(name), _) => name,
+            (None, Some(ident)) => rename(
+                &ident.to_string(),
+                variant
+                    .rename_all
+                    .as_deref(),
+            ),
+            (None, None) => String::new(),
+        };
+        Some(SerdeField {
+            name,
+            ty: &field.ty,
+        })
+    }
+}
+
+/// Applies a serde `rename_all` rule to the name of a variant or a field.
+fn rename(
+    name: &str,
+    rule: Option<&str>,
+) -> String {
+    match rule {
+        Some("lowercase") => name.to_lowercase(),
+        Some("UPPERCASE") => name.to_uppercase(),
+        Some("PascalCase") => name.to_upper_camel_case(),
+        Some("camelCase") => name.to_lower_camel_case(),
+        Some("snake_case") => name.to_snake_case(),
+        Some("SCREAMING_SNAKE_CASE") => name.to_shouty_snake_case(),
+        Some("kebab-case") => name.to_kebab_case(),
+        Some("SCREAMING-KEBAB-CASE") => name.to_shouty_kebab_case(),
+        _ => name.to_string(),
+    }
+}
+
+fn object_schema(properties: impl IntoIterator<Item = (String, Value)>) -> Value {
+    let properties = properties
+        .into_iter()
+        .collect::<Map<_, _>>();
+    let required = properties
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// The schema of a field type, types without a known JSON representation only have a `title`.
+fn type_schema(ty: &Type) -> Value {
+    match ty {
+        Type::Reference(reference) => type_schema(&reference.elem),
+        Type::Paren(paren) => type_schema(&paren.elem),
+        Type::Group(group) => type_schema(&group.elem),
+        Type::Tuple(tuple)
+            if tuple
+                .elems
+                .is_empty() =>
+        {
+            json!({ "type": "null" })
+        }
+        Type::Tuple(tuple) => tuple_schema(
+            tuple
+                .elems
+                .iter(),
+        ),
+        Type::Array(array) => json!({ "type": "array", "items": type_schema(&array.elem) }),
+        Type::Slice(slice) => json!({ "type": "array", "items": type_schema(&slice.elem) }),
+        Type::Path(type_path) => {
+            let Some(segment) = type_path
+                .path
+                .segments
+                .last()
+            else {
+                return unknown_type_schema(ty);
+            };
+            let generic_types = match &segment.arguments {
+                PathArguments::AngleBracketed(arguments) => arguments
+                    .args
+                    .iter()
+                    .filter_map(|argument| match argument {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+                _ => Vec::new(),
+            };
+            match (
+                segment
+                    .ident
+                    .to_string()
+                    .as_str(),
+                generic_types.as_slice(),
+            ) {
+                ("bool", []) => json!({ "type": "boolean" }),
+                ("i8" | "i16" | "i32" | "i64" | "i128" | "isize", []) => json!({ "type": "integer" }),
+                ("u8" | "u16" | "u32" | "u64" | "u128" | "usize", []) => json!({ "type": "integer", "minimum": 0 }),
+                ("f32" | "f64", []) => json!({ "type": "number" }),
+                ("String" | "str" | "char" | "PathBuf", []) => json!({ "type": "string" }),
+                ("Box" | "Rc" | "Arc" | "Cow", [inner]) => type_schema(inner),
+                ("Option", [inner]) => json!({ "anyOf": [type_schema(inner), { "type": "null" }] }),
+                ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner]) => json!({ "type": "array", "items": type_schema(inner) }),
+                ("HashMap" | "BTreeMap", [_, value]) => json!({ "type": "object", "additionalProperties": type_schema(value) }),
+                _ => unknown_type_schema(ty),
+            }
+        }
+        _ => unknown_type_schema(ty),
+    }
+}
+
+fn tuple_schema<'a>(types: impl Iterator<Item = &'a Type>) -> Value {
+    let items = types
+        .map(type_schema)
+        .collect::<Vec<_>>();
+    json!({
+        "type": "array",
+        "prefixItems": items,
+        "minItems": items.len(),
+        "maxItems": items.len(),
+    })
+}
+
+fn unknown_type_schema(ty: &Type) -> Value {
+    json!({ "title": type_to_string(ty) })
+}
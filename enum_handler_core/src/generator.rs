@@ -1,7 +1,9 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Ident, Path, Type};
 
 use crate::{
+    config::Config,
     enum_handler_args::EnumHandlerArgs,
     model::{Enum, EnumVariant, EnumVariantType},
     Result,
@@ -11,47 +13,140 @@ impl Enum {
     pub fn generate_trait(
         &self,
         args: &EnumHandlerArgs,
+        handlers: &[TokenStream],
     ) -> Result<TokenStream> {
-        let visibility = args.visibility(&self.vis)?;
-        let trait_name = args.get_trait_name(&self.name);
-        let enum_name = &self.name;
+        let visibility = &self
+            .config
+            .visibility;
+        let trait_name = self
+            .config
+            .get_trait_name(args);
+        let enum_path = &self.path;
 
-        let handler_name = args.get_handler_name();
-        let return_type = args.get_return_type()?;
-
-        let handlers = self.generate_handlers(args)?;
+        let handler_name = &self
+            .config
+            .handler_name;
+        let output_name = if self.has_output_enum() {
+            Some(
+                &self
+                    .config
+                    .output_name,
+            )
+        } else {
+            None
+        };
+        let return_type = self.get_output_type();
 
         let match_arms = self
-            .variants
-            .iter()
-            .map(|v| v.generate_match_arm(args))
+            .handler_variants()
+            .map(|v| v.generate_match_arm(args, output_name, None))
             .collect::<Vec<_>>();
 
-        let async_trait = if args.use_async_trait_macro() {
-            quote! {#[async_trait::async_trait]}
+        // `try_on` wraps the error of the failed handler method with the variant name
+        let try_dispatcher = if args.is_fallible() {
+            let error_name = &self
+                .config
+                .error_name;
+            let try_match_arms = self
+                .handler_variants()
+                .map(|v| v.generate_match_arm(args, output_name, Some(error_name)))
+                .collect::<Vec<_>>();
+            let try_return_type: Type = parse_quote! { ::core::result::Result<#return_type, #error_name> };
+            Some((
+                &self
+                    .config
+                    .try_handler_name,
+                try_return_type,
+                try_match_arms,
+            ))
         } else {
-            quote! {}
+            None
         };
+        let return_type = self
+            .config
+            .get_fallible_return_type(return_type);
+
+        let associated_output = generate_associated_output(args)?;
+
+        let async_trait = args.get_async_trait_attribute();
         let async_fn = if args.is_async() {
             quote! {async}
         } else {
             quote! {}
         };
 
+        if args.is_boxed_async() {
+            let boxed = &self
+                .config
+                .box_path;
+            let boxed_receiver = args.get_boxed_receiver();
+            let move_or_borrow = if args.is_move_arguments() {
+                quote! {}
+            } else {
+                quote! {&'a}
+            };
+            let try_dispatcher = try_dispatcher.map(|(try_handler_name, try_return_type, try_match_arms)| {
+                let boxed_future = boxed_future(args, boxed, &try_return_type);
+                quote! {
+                    fn #try_handler_name<'a>(#boxed_receiver, e: #move_or_borrow #enum_path) -> #boxed_future {
+                        #boxed::pin(async move {
+                            match (e) {
+                                #(#try_match_arms)*
+                            }
+                        })
+                    }
+                }
+            });
+            let boxed_future = boxed_future(args, boxed, &return_type);
+            let supertraits = if args.is_async_send() {
+                quote! {: ::core::marker::Send + ::core::marker::Sync}
+            } else {
+                quote! {}
+            };
+
+            return Ok(quote! {
+                #visibility trait #trait_name #supertraits {
+                    #associated_output
+                    fn #handler_name<'a>(#boxed_receiver, e: #move_or_borrow #enum_path) -> #boxed_future {
+                        #boxed::pin(async move {
+                            match (e) {
+                                #(#match_arms)*
+                            }
+                        })
+                    }
+                    #try_dispatcher
+                    #(#handlers)*
+                }
+            });
+        }
+
         let move_or_borrow = if args.is_move_arguments() {
             quote! {}
         } else {
             quote! {&}
         };
+        let receiver = args.get_receiver();
+
+        let try_dispatcher = try_dispatcher.map(|(try_handler_name, try_return_type, try_match_arms)| {
+            quote! {
+                #async_fn fn #try_handler_name(#receiver, e: #move_or_borrow #enum_path) -> #try_return_type {
+                    match (e) {
+                        #(#try_match_arms)*
+                    }
+                }
+            }
+        });
 
         let output = quote! {
             #async_trait
             #visibility trait #trait_name {
-                #async_fn fn #handler_name(&self, e: #move_or_borrow #enum_name) -> #return_type {
+                #associated_output
+                #async_fn fn #handler_name(#receiver, e: #move_or_borrow #enum_path) -> #return_type {
                     match (e) {
                         #(#match_arms)*
                     }
                 }
+                #try_dispatcher
                 #(#handlers)*
             }
         };
@@ -59,32 +154,49 @@ impl Enum {
         Ok(output)
     }
 
+    /// The handler methods of the trait are reused if they are the same for the mock.
     pub fn generate_mock(
         &self,
         args: &EnumHandlerArgs,
+        trait_handlers: &[TokenStream],
     ) -> Result<TokenStream> {
+        // the mock has no default implementations and returns `mock_output` instead of `Self::Output`
+        let same_handlers = !args.is_default_implementation() && !args.is_associated_output();
         let mock_args = args.clone_for_mock();
         let args = &mock_args;
 
-        let visibility = args.visibility(&self.vis)?;
-        let trait_name = args.get_trait_name(&self.name);
-
-        let handlers = self.generate_handlers(args)?;
+        if args.is_boxed_async() {
+            return self.generate_boxed_mock(args);
+        }
 
-        let mock_name = args.get_mock_name()?;
+        let visibility = &self
+            .config
+            .visibility;
+        let trait_name = &self
+            .config
+            .trait_name;
 
-        let async_trait = if args.use_async_trait_macro() {
-            quote! {#[async_trait::async_trait]}
+        let mock_handlers;
+        let handlers = if same_handlers {
+            trait_handlers
         } else {
-            quote! {}
+            mock_handlers = self.generate_handlers(args)?;
+            &mock_handlers
         };
 
+        let mock_name = args.get_mock_name()?;
+        let associated_output = generate_associated_output(args)?;
+
+        let async_trait = args.get_async_trait_attribute();
+        let mockall = args.get_mockall_path();
+
         let output = quote! {
             #[cfg(test)]
-            mockall::mock! {
+            #mockall::mock! {
                 #visibility  #mock_name {}
                 #async_trait
                 impl #trait_name for #mock_name {
+                    #associated_output
                     #(#handlers)*
                 }
             }
@@ -93,13 +205,209 @@ impl Enum {
         Ok(output)
     }
 
+    /// The enum returned by the common handler method if variants have their own return types (`returns = "Type"`).
+    pub fn generate_output_enum(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> TokenStream {
+        let visibility = &self
+            .config
+            .visibility;
+        let trait_name = &self
+            .config
+            .trait_name;
+        let handler_name = &self
+            .config
+            .handler_name;
+        let output_name = &self
+            .config
+            .output_name;
+        let variants = self
+            .handler_variants()
+            .map(|v| {
+                let variant_name = &v.name;
+                let return_type = v.get_return_type(args);
+                quote! {
+                    #variant_name(#return_type),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let doc = format!(
+            " The results of the handler methods, returned by [`{}::{}`].",
+            trait_name, handler_name
+        );
+        quote! {
+            #[doc = #doc]
+            #visibility enum #output_name {
+                #(#variants)*
+            }
+        }
+    }
+
+    /// The error of the `try_` handler method with `error_type`: the name of the failed variant and the error of its handler method.
+    /// The error type is a defaulted type parameter, so `Error` is only implemented if the `error_type` implements it,
+    /// and ad-hoc error types like `String` can be used.
+    pub fn generate_error(
+        &self,
+        error_type: &Type,
+    ) -> TokenStream {
+        let visibility = &self
+            .config
+            .visibility;
+        let trait_name = &self
+            .config
+            .trait_name;
+        let try_handler_name = &self
+            .config
+            .try_handler_name;
+        let error_name = &self
+            .config
+            .error_name;
+
+        let doc = format!(
+            " The error returned by [`{}::{}`], the handler method of `variant` failed with `source`.",
+            trait_name, try_handler_name
+        );
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug)]
+            #visibility struct #error_name<E = #error_type> {
+                pub variant: &'static str,
+                pub source: E,
+            }
+
+            impl<E> ::core::fmt::Display for #error_name<E> {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::write!(f, "the handler of {} failed", self.variant)
+                }
+            }
+
+            impl<E: ::core::error::Error + 'static> ::core::error::Error for #error_name<E> {
+                fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+                    ::core::option::Option::Some(&self.source)
+                }
+            }
+        }
+    }
+
+    /// mockall can't mock methods returning futures that borrow `self`, so the expectations are set on
+    /// inherent sync methods and the trait implementation wraps their results in ready futures.
+    fn generate_boxed_mock(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> Result<TokenStream> {
+        let visibility = &self
+            .config
+            .visibility;
+        let trait_name = &self
+            .config
+            .trait_name;
+        let mock_name = args.get_mock_name()?;
+        // mockall prefixes the name of the generated struct
+        let mock_struct_name = format_ident!("Mock{}", mock_name);
+        let associated_output = generate_associated_output(args)?;
+        // the expectations are sync methods
+        let sync_args = args.clone_for_sync_flavour();
+
+        let mut expectations = Vec::new();
+        let mut handlers = Vec::new();
+        for v in self.handler_variants() {
+            let handler_name = &v.handler_name;
+            let return_type = v.get_handler_return_type(args);
+            let mut params = v.generate_parameters(args);
+            let mut arguments = v.generate_arguments(args);
+            arguments.insert(0, quote! {self});
+
+            let mut sync_params = v.generate_parameters(&sync_args);
+            sync_params.insert(0, quote! {&self});
+            expectations.push(quote! {
+                fn #handler_name(#(#sync_params),*) -> #return_type;
+            });
+
+            if !v.is_boxed_async(args) {
+                handlers.push(quote! {
+                    fn #handler_name(#(#sync_params),*) -> #return_type {
+                        #mock_struct_name::#handler_name(#(#arguments),*)
+                    }
+                });
+                continue;
+            }
+            params.insert(0, quote! {&'a self});
+            let boxed = &self
+                .config
+                .box_path;
+            let boxed_future = boxed_future(args, boxed, return_type);
+            handlers.push(quote! {
+                fn #handler_name<'a>(#(#params),*) -> #boxed_future {
+                    let output = #mock_struct_name::#handler_name(#(#arguments),*);
+                    #boxed::pin(async move { output })
+                }
+            });
+        }
+
+        let mockall = args.get_mockall_path();
+        Ok(quote! {
+            #[cfg(test)]
+            #mockall::mock! {
+                #visibility #mock_name {
+                    #(#expectations)*
+                }
+            }
+            #[cfg(test)]
+            impl #trait_name for #mock_struct_name {
+                #associated_output
+                #(#handlers)*
+            }
+        })
+    }
+
+    /// Converts between the `remote` enum and the mirror enum, so the compiler checks that both have the same variants and fields.
+    pub fn generate_remote_check(&self) -> TokenStream {
+        let enum_path = &self.path;
+        let mirror_path = Path::from(
+            self.name
+                .clone(),
+        );
+        let from_remote = self
+            .variants
+            .iter()
+            .map(|v| v.generate_remote_conversion(enum_path, &mirror_path))
+            .collect::<Vec<_>>();
+        let into_remote = self
+            .variants
+            .iter()
+            .map(|v| v.generate_remote_conversion(&mirror_path, enum_path))
+            .collect::<Vec<_>>();
+
+        // the unknown variants of a `#[non_exhaustive]` remote enum can't be converted, only the mirror is checked
+        let unknown = self
+            .unknown_variant
+            .is_some()
+            .then(|| {
+                quote! {
+                    #[allow(unreachable_patterns)]
+                    _ => ::core::unreachable!(),
+                }
+            });
+
+        quote! {
+            const _: fn(#enum_path) -> #mirror_path = |e| match e {
+                #(#from_remote)*
+                #unknown
+            };
+            const _: fn(#mirror_path) -> #enum_path = |e| match e {
+                #(#into_remote)*
+            };
+        }
+    }
+
     pub fn generate_handlers(
         &self,
         args: &EnumHandlerArgs,
     ) -> Result<Vec<TokenStream>> {
-        self.variants
-            .iter()
-            .map(|v| v.generate_handler(args))
+        self.handler_variants()
+            .map(|v| v.generate_handler(args, &self.config))
             .collect()
     }
 }
@@ -108,59 +416,118 @@ impl EnumVariant {
     pub(crate) fn generate_handler(
         &self,
         args: &EnumHandlerArgs,
+        config: &Config,
     ) -> Result<TokenStream> {
-        let handler_name = self.get_variant_handler_name(args);
-        let return_type = &self.return_type;
+        let handler_name = &self.handler_name;
+        let return_value = if self.has_own_return_type { &config.own_return_value } else { &config.return_value };
+        let return_type = self.get_handler_return_type(args);
+        let mut params = self.generate_parameters(args);
+
+        if self.is_boxed_async(args) {
+            params.insert(0, args.get_boxed_receiver());
+            let boxed_future = boxed_future(args, &config.box_path, return_type);
+            if args.is_default_implementation() {
+                let boxed = &config.box_path;
+                return Ok(quote! {
+                    fn #handler_name<'a>(#(#params),*) -> #boxed_future {
+                        #boxed::pin(async move { #return_value })
+                    }
+                });
+            }
+            return Ok(quote! {
+                fn #handler_name<'a>(#(#params),*) -> #boxed_future;
+            });
+        }
+        params.insert(0, args.get_receiver());
+
+        let async_fn = if self.is_async(args) {
+            quote! {async}
+        } else {
+            quote! {}
+        };
+
+        if args.is_default_implementation() {
+            Ok(quote! {
+                #async_fn fn #handler_name(#(#params),*) -> #return_type {
+                    #return_value
+                }
+            })
+        } else {
+            Ok(quote! {
+                #async_fn fn #handler_name(#(#params),*) -> #return_type;
+            })
+        }
+    }
+
+    /// The parameters of the variant handler method, without the receiver.
+    pub(crate) fn generate_parameters(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> Vec<TokenStream> {
+        // boxed futures borrow the arguments for the lifetime of the future
+        let reference = if self.is_boxed_async(args) {
+            quote! {&'a}
+        } else {
+            quote! {&}
+        };
         let mut params = self
             .fields
             .iter()
             .enumerate()
             .map(|(i, (name, ty))| {
                 let name = self.parameter_name(name, i);
-                let ty_string = quote! {#ty}.to_string();
                 if args.is_move_arguments() {
                     quote! {
                         #name: #ty
                     }
-                } else if ty_string == "String" {
+                } else if is_string(ty) {
                     quote! {
-                        #name: &str
+                        #name: #reference str
                     }
                 } else {
                     quote! {
-                        #name: &#ty
+                        #name: #reference #ty
                     }
                 }
             })
             .collect::<Vec<_>>();
 
-        params.insert(0, quote! {&self});
-
-        let async_fn = if args.is_async() {
-            quote! {async}
-        } else {
-            quote! {}
-        };
+        if args.is_pass_event() {
+            let enum_path = &self.enum_path;
+            params.insert(0, quote! {e: #reference #enum_path});
+        }
+        params
+    }
 
-        if args.is_default_implementation() {
-            let return_value = args.get_return_value()?;
-            Ok(quote! {
-                #async_fn fn #handler_name(#(#params),*) -> #return_type {
-                    #return_value
-                }
-            })
-        } else {
-            Ok(quote! {
-                #async_fn fn #handler_name(#(#params),*) -> #return_type;
+    /// The arguments passed to the variant handler method, matching [`Self::generate_parameters`].
+    pub(crate) fn generate_arguments(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> Vec<TokenStream> {
+        let mut arguments = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| {
+                let name = self.parameter_name(name, i);
+                quote! { #name }
             })
+            .collect::<Vec<_>>();
+        if args.is_pass_event() {
+            arguments.insert(0, quote! { e });
         }
+        arguments
     }
 
+    /// The match arm of the common handler method, the result is wrapped in the output enum if given.
+    /// With `error_name` the error is wrapped together with the name of the variant.
     pub(crate) fn generate_match_arm(
         &self,
         args: &EnumHandlerArgs,
+        output_name: Option<&Ident>,
+        error_name: Option<&Ident>,
     ) -> TokenStream {
-        let enum_name = &self.enum_name;
+        let enum_path = &self.enum_path;
         let variant_name = &self.name;
         let parameters = self
             .fields
@@ -171,23 +538,117 @@ impl EnumVariant {
                 quote! { #name }
             })
             .collect::<Vec<_>>();
-        let handler_name = self.get_variant_handler_name(args);
-        let await_fn = if args.is_async() {
+        let arguments = self.generate_arguments(args);
+        let handler_name = &self.handler_name;
+        let await_fn = if self.is_async(args) {
             quote! {.await}
         } else {
             quote! {}
         };
+        let mut call = quote! { self.#handler_name( #(#arguments),* ) #await_fn };
+        if let Some(output_name) = output_name {
+            if args.is_fallible() {
+                call = quote! { #call.map(#output_name::#variant_name) };
+            } else {
+                call = quote! { #output_name::#variant_name(#call) };
+            }
+        }
+        if let Some(error_name) = error_name {
+            let variant = variant_name.to_string();
+            call = quote! {
+                #call.map_err(|source| #error_name {
+                    variant: #variant,
+                    source,
+                })
+            };
+        }
 
         match self.variant_type {
             EnumVariantType::Unit => quote! {
-                #enum_name::#variant_name => { self.#handler_name() #await_fn }
+                #enum_path::#variant_name => { #call }
             },
             EnumVariantType::Struct => quote! {
-                #enum_name::#variant_name { #(#parameters),* } => { self.#handler_name( #(#parameters),* ) #await_fn }
+                #enum_path::#variant_name { #(#parameters),* } => { #call }
             },
             EnumVariantType::Tuple => quote! {
-                #enum_name::#variant_name( #(#parameters),* ) => { self.#handler_name( #(#parameters),* ) #await_fn }
+                #enum_path::#variant_name( #(#parameters),* ) => { #call }
+            },
+            // the enum may be exhaustive in the crate that defines it
+            EnumVariantType::Unknown => quote! {
+                #[allow(unreachable_patterns)]
+                _ => { #call }
             },
         }
     }
+
+    pub(crate) fn generate_remote_conversion(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> TokenStream {
+        let variant_name = &self.name;
+        let parameters = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| self.parameter_name(name, i))
+            .collect::<Vec<_>>();
+
+        match self.variant_type {
+            EnumVariantType::Unit => quote! {
+                #from::#variant_name => #to::#variant_name,
+            },
+            EnumVariantType::Struct => quote! {
+                #from::#variant_name { #(#parameters),* } => #to::#variant_name { #(#parameters),* },
+            },
+            EnumVariantType::Tuple => quote! {
+                #from::#variant_name( #(#parameters),* ) => #to::#variant_name( #(#parameters),* ),
+            },
+            EnumVariantType::Unknown => unreachable!("the unknown variants have no counterpart"),
+        }
+    }
+}
+
+/// `String` fields are passed as `&str`, compared without formatting the type for every field.
+fn is_string(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.qself.is_none() && type_path.path.is_ident("String"))
+}
+
+/// `Pin<Box<dyn Future<Output = R> + Send + 'a>>`, the return type of the handler methods with `async_style = "boxed"`.
+/// The `Send` bound is left out with `async_send = false`.
+pub(crate) fn boxed_future(
+    args: &EnumHandlerArgs,
+    boxed: &Path,
+    return_type: &Type,
+) -> TokenStream {
+    let send = if args.is_async_send() {
+        quote! {+ ::core::marker::Send}
+    } else {
+        quote! {}
+    };
+    quote! {
+        ::core::pin::Pin<#boxed<dyn ::core::future::Future<Output = #return_type> #send + 'a>>
+    }
+}
+
+/// `type Output: Bounds;` in the trait, `type Output = MockOutput;` in the mock, nothing without `associated_output`.
+fn generate_associated_output(args: &EnumHandlerArgs) -> Result<TokenStream> {
+    if !args.is_associated_output() {
+        return Ok(quote! {});
+    }
+    if args.is_mock() {
+        let mock_output = args.get_mock_output()?;
+        return Ok(quote! {
+            type Output = #mock_output;
+        });
+    }
+    let bounds = args.get_output_bounds()?;
+    if bounds.is_empty() {
+        return Ok(quote! {
+            type Output;
+        });
+    }
+    Ok(quote! {
+        type Output: #(#bounds)+*;
+    })
 }
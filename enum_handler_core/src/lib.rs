@@ -1,99 +1,346 @@
 #![doc = include_str!("../README.md")]
 
+mod accessors;
+mod adapter;
+mod blanket;
+mod builder;
+mod callback;
+pub mod config;
+mod debug;
+mod diagram;
 pub mod enum_handler_args;
 mod error;
+mod export;
 mod generator;
-mod model;
+mod lock;
+mod meta;
+pub mod model;
 
 #[cfg(test)]
 mod tests;
 
-use std::{env, fs::OpenOptions, io::Write, process};
+use std::collections::HashSet;
 
-use darling::FromDeriveInput;
-use enum_handler_args::EnumHandlerArgs;
+use enum_handler_args::{EnumHandlerArgs, HELPER_ATTRIBUTE};
 use model::Enum;
 use proc_macro2::TokenStream;
+use quote::ToTokens;
 use syn::*;
 
+pub use builder::{find_enums, Generator};
+#[doc(hidden)]
+pub use debug::format_tokens;
 pub use error::{Error, Result};
 
 // https://astexplorer.net/
 
 pub fn enum_handler_core(input: TokenStream) -> Result<TokenStream> {
     let derive_input: DeriveInput = syn::parse2::<DeriveInput>(input.clone())?;
-    let args = EnumHandlerArgs::from_derive_input(&derive_input)?;
+    let args_list = EnumHandlerArgs::list_from_derive_input(&derive_input)?;
 
-    let e = Enum::new(&derive_input, &args)?;
+    let traits = Traits::new(&derive_input, args_list)?;
+    let output = generate(&derive_input, &traits)?;
 
+    write_description_files(&derive_input, &traits)?;
+    debug::write_debug_file(&derive_input.ident, &input, &output)?;
+    Ok(output)
+}
+
+/// Entry point for the `#[enum_handler(...)]` attribute macro.
+///
+/// Unlike the derive, the attribute owns the enum: it is emitted again together with the
+/// generated code, with all `#[enum_handler]` helper attributes removed.
+/// No derives are added, none of the generated helpers require a trait implemented by the enum.
+/// Further `#[enum_handler(...)]` attributes on the enum generate additional traits.
+pub fn enum_handler_attribute_core(
+    attr: TokenStream,
+    item: TokenStream,
+) -> Result<TokenStream> {
+    let mut derive_input: DeriveInput = syn::parse2::<DeriveInput>(item.clone())?;
+    let mut args_list = vec![EnumHandlerArgs::from_attribute(attr)?];
+    if derive_input
+        .attrs
+        .iter()
+        .any(|attr| {
+            attr.path()
+                .is_ident(HELPER_ATTRIBUTE)
+        })
+    {
+        args_list.extend(EnumHandlerArgs::list_from_derive_input(&derive_input)?);
+    }
+
+    let traits = Traits::new(&derive_input, args_list)?;
+    let generated = generate(&derive_input, &traits)?;
+    write_description_files(&derive_input, &traits)?;
+
+    strip_helper_attributes(&mut derive_input);
+    let mut output = derive_input.to_token_stream();
+    output.extend(generated);
+
+    debug::write_debug_file(&derive_input.ident, &item, &output)?;
+    Ok(output)
+}
+
+/// The options and the enum of every generated trait, resolved once for the traits, the mocks, the helpers and the
+/// description files.
+struct Traits {
+    /// The options of every `#[enum_handler(...)]` attribute.
+    args_list: Vec<EnumHandlerArgs>,
+    /// The options of every generated trait, `flavours = "both"` describes a sync and an async trait.
+    flavour_args_list: Vec<EnumHandlerArgs>,
+    enums: Vec<Enum>,
+}
+
+impl Traits {
+    fn new(
+        derive_input: &DeriveInput,
+        args_list: Vec<EnumHandlerArgs>,
+    ) -> Result<Self> {
+        let flavour_args_list = expand_flavours(&derive_input.ident, &args_list)?;
+        let enums = flavour_args_list
+            .iter()
+            .map(|args| Enum::new(derive_input, args))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut trait_names = HashSet::new();
+        let mut mock_names = HashSet::new();
+        for (args, e) in flavour_args_list
+            .iter()
+            .zip(&enums)
+        {
+            let trait_name = &e
+                .config
+                .trait_name;
+            if !trait_names.insert(trait_name) {
+                return Err(Error::DuplicateTraitName(trait_name.to_string()));
+            }
+            if args.is_generate_mock() {
+                let mock_name = args.get_mock_name()?;
+                if !mock_names.insert(mock_name.clone()) {
+                    return Err(Error::DuplicateMockName(mock_name.to_string()));
+                }
+            }
+        }
+
+        Ok(Traits {
+            args_list,
+            flavour_args_list,
+            enums,
+        })
+    }
+}
+
+fn generate(
+    derive_input: &DeriveInput,
+    traits: &Traits,
+) -> Result<TokenStream> {
+    let enum_name = &derive_input.ident;
+    let Traits {
+        args_list,
+        flavour_args_list,
+        enums,
+    } = traits;
+
+    let mut output = TokenStream::new();
+    let mut output_names = HashSet::new();
+    let mut handler_method_infos = Vec::new();
+    for (args, e) in flavour_args_list
+        .iter()
+        .zip(enums)
+    {
+        output.extend(generate_single(e, args, &mut output_names)?);
+        if args.is_metadata() {
+            handler_method_infos.extend(e.generate_handler_method_infos(args));
+        }
+    }
+    if let Some(e) = flavour_args_list
+        .iter()
+        .zip(enums)
+        .find_map(|(args, e)| {
+            args.is_metadata()
+                .then_some(e)
+        })
+    {
+        output.extend(meta::generate_meta_impl(
+            enum_name,
+            &e.config
+                .crate_path,
+            &handler_method_infos,
+        ));
+    }
+
+    // the sync trait of `flavours = "both"` is followed by its async trait, the callback is called once for both
+    let mut index = 0;
+    for args in args_list {
+        if let Some(callback) = args.get_callback() {
+            output.extend(enums[index].generate_callback(callback));
+        }
+        if args.is_both_flavours()? {
+            let (sync_args, async_args) = (&flavour_args_list[index], &flavour_args_list[index + 1]);
+            output.extend(enums[index].generate_flavour_adapters(sync_args, &enums[index + 1], async_args)?);
+            index += 2;
+        } else {
+            index += 1;
+        }
+    }
+
+    // accessors and kind enums belong to the enum, not to a trait, so they are generated once
+    if let Some(e) = flavour_args_list
+        .iter()
+        .zip(enums)
+        .find_map(|(args, e)| {
+            args.is_accessors()
+                .then_some(e)
+        })
+    {
+        output.extend(e.generate_accessors());
+    }
+    let mut kind_names = HashSet::new();
+    for (args, e) in flavour_args_list
+        .iter()
+        .zip(enums)
+    {
+        if let Some(kind_name) = args.get_kind_name()? {
+            if kind_names.insert(kind_name.clone()) {
+                output.extend(e.generate_kind(&kind_name));
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// The options of every generated trait, `flavours = "both"` describes a sync and an async trait.
+fn expand_flavours(
+    enum_name: &Ident,
+    args_list: &[EnumHandlerArgs],
+) -> Result<Vec<EnumHandlerArgs>> {
+    let mut flavour_args_list = Vec::new();
+    for args in args_list {
+        if args.is_both_flavours()? {
+            flavour_args_list.push(args.clone_for_sync_flavour());
+            flavour_args_list.push(args.clone_for_async_flavour(enum_name));
+        } else {
+            flavour_args_list.push(args.clone());
+        }
+    }
+    Ok(flavour_args_list)
+}
+
+/// Generates a trait with its mock and helpers, output enums shared by several traits are generated once.
+fn generate_single(
+    e: &Enum,
+    args: &EnumHandlerArgs,
+    output_names: &mut HashSet<Ident>,
+) -> Result<TokenStream> {
     if e.variants
         .is_empty()
     {
         return Err(Error::NoVariants);
     }
 
-    let mut output = e.generate_trait(&args)?;
+    // a single async variant makes the trait and its dispatcher async
+    let async_variants_args;
+    let args = if !args.is_async() && e.has_async_variant(args) {
+        async_variants_args = args.clone_for_async_variants();
+        &async_variants_args
+    } else {
+        args
+    };
+
+    if args.is_associated_output() && e.has_output_enum() {
+        return Err(Error::AssociatedOutputWithVariantReturns);
+    }
+
+    if args.is_pass_event() {
+        if let Some(variant) = e
+            .variants
+            .iter()
+            .find(|v| v.has_field_named("e"))
+        {
+            return Err(Error::PassEventFieldConflict(
+                variant
+                    .name
+                    .to_string(),
+            ));
+        }
+    }
+
+    let handlers = e.generate_handlers(args)?;
+    let mut output = e.generate_trait(args, &handlers)?;
+
+    if e.has_output_enum()
+        && output_names.insert(
+            e.config
+                .output_name
+                .clone(),
+        )
+    {
+        output.extend(e.generate_output_enum(args));
+    }
+
+    if let Some(error_type) = &e
+        .config
+        .error_type
+    {
+        output.extend(e.generate_error(error_type));
+    }
+
+    if args.is_blanket_impls() {
+        output.extend(e.generate_blanket_impls(args));
+    }
+
+    if args.is_lock_impls() {
+        let mut_args = args.clone_for_mut_trait();
+        let mut_handlers = e.generate_handlers(&mut_args)?;
+        output.extend(e.generate_trait(&mut_args, &mut_handlers)?);
+        output.extend(e.generate_lock_impls(args, &mut_args)?);
+    }
+
+    if args.is_remote() {
+        output.extend(e.generate_remote_check());
+    }
 
     if args.is_generate_mock() {
-        let mock = e.generate_mock(&args);
-        output.extend(mock);
+        output.extend(e.generate_mock(args, &handlers)?);
     }
 
-    write_debug_file(&input, &output)?;
     Ok(output)
 }
 
-// FIXME: concurrent writes to the debug file
-fn write_debug_file(
-    input: &TokenStream,
-    output: &TokenStream,
+/// The interface export and the diagrams, if enabled by their environment variables.
+fn write_description_files(
+    derive_input: &DeriveInput,
+    traits: &Traits,
 ) -> Result<()> {
-    static DEBUG_FILE_ENV: &str = "ENUM_HANDLER_DEBUG";
-    static FORMAT_DEBUG_FILE_ENV: &str = "ENUM_HANDLER_DEBUG_FORMAT";
-
-    static MAX_FILE_SIZE: u64 = 1024 * 128;
-    let debug_filename = env::var(DEBUG_FILE_ENV).unwrap_or_default();
-    let format_debug_file = !env::var(FORMAT_DEBUG_FILE_ENV)
-        .unwrap_or_default()
-        .is_empty();
-    if !debug_filename.is_empty() {
-        let mut append = true;
-        if let Ok(metadata) = std::fs::metadata(&debug_filename) {
-            if metadata.len() > MAX_FILE_SIZE {
-                append = false;
-            }
-        }
+    export::write_export_files(derive_input, &traits.flavour_args_list, &traits.enums)?;
+    diagram::write_diagram_files(derive_input, &traits.flavour_args_list, &traits.enums)
+}
+
+fn strip_helper_attributes(derive_input: &mut DeriveInput) {
+    fn is_helper(attr: &Attribute) -> bool {
+        attr.path()
+            .is_ident(HELPER_ATTRIBUTE)
+    }
 
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(append)
-            .open(&debug_filename)
-            .map_err(|_| Error::CannotWriteDebugFile(debug_filename.clone(), DEBUG_FILE_ENV.to_string()))?;
-
-        let trace = format!(
-            r#"
-
-// ************************************************************
-// Given input:
-{}
-// ------------------------------------------------------------
-// Generated output:
-{}
-// ************************************************************
-
-"#,
-            input, output
-        );
-
-        file.write(trace.as_bytes())
-            .map_err(|_| Error::CannotWriteDebugFile(debug_filename.clone(), DEBUG_FILE_ENV.to_string()))?;
-
-        if format_debug_file {
-            let _ = process::Command::new("rustfmt")
-                .arg("--edition=2021")
-                .arg(&debug_filename)
-                .output();
+    derive_input
+        .attrs
+        .retain(|attr| !is_helper(attr));
+    if let Data::Enum(data_enum) = &mut derive_input.data {
+        for variant in data_enum
+            .variants
+            .iter_mut()
+        {
+            variant
+                .attrs
+                .retain(|attr| !is_helper(attr));
+            for field in variant
+                .fields
+                .iter_mut()
+            {
+                field
+                    .attrs
+                    .retain(|attr| !is_helper(attr));
+            }
         }
     }
-    Ok(())
 }
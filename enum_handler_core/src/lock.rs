@@ -0,0 +1,135 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::{enum_handler_args::EnumHandlerArgs, generator::boxed_future, model::Enum, Error, Result};
+
+impl Enum {
+    /// Implements the trait for the locks of every implementor `T` of the `Mut` trait, whose methods take `&mut self`.
+    /// Every handler method locks for the duration of the call:
+    /// - sync traits are implemented for `std::sync::Mutex<T>` and `RwLock<T>`, a poisoned lock is used anyway
+    /// - async traits are implemented for the `async_mutex`, which is locked across the `.await` of the handler method
+    pub fn generate_lock_impls(
+        &self,
+        args: &EnumHandlerArgs,
+        mut_args: &EnumHandlerArgs,
+    ) -> Result<TokenStream> {
+        let trait_name = self
+            .config
+            .get_trait_name(args);
+        let mut_trait_name = self
+            .config
+            .get_trait_name(mut_args);
+        let output = if args.is_associated_output() {
+            quote! { type Output = <T as #mut_trait_name>::Output; }
+        } else {
+            quote! {}
+        };
+
+        let async_variants = self
+            .handler_variants()
+            .filter(|v| v.is_async(args))
+            .count();
+        if async_variants == 0 {
+            let crate_path = &self
+                .config
+                .crate_path;
+            let mutex_handlers = self.generate_lock_handlers(args, mut_trait_name, quote! {lock});
+            let rw_lock_handlers = self.generate_lock_handlers(args, mut_trait_name, quote! {write});
+            return Ok(quote! {
+                impl<T: #mut_trait_name + ?::core::marker::Sized> #trait_name for #crate_path::__private::Mutex<T> {
+                    #output
+                    #(#mutex_handlers)*
+                }
+                impl<T: #mut_trait_name + ?::core::marker::Sized> #trait_name for #crate_path::__private::RwLock<T> {
+                    #output
+                    #(#rw_lock_handlers)*
+                }
+            });
+        }
+        if async_variants
+            < self
+                .handler_variants()
+                .count()
+        {
+            return Err(Error::LockImplsWithMixedAsync);
+        }
+
+        let async_mutex = args
+            .get_async_mutex()
+            .ok_or(Error::LockImplsRequireAsyncMutex)?;
+        let async_trait = args.get_async_trait_attribute();
+        // the guard of the `async_trait` futures is held across `.await`
+        let send_bound = if args.use_async_trait_macro() && args.is_async_send() {
+            quote! {+ ::core::marker::Send}
+        } else {
+            quote! {}
+        };
+        let handlers = self
+            .handler_variants()
+            .map(|v| {
+                let handler_name = &v.handler_name;
+                let return_type = v.get_handler_return_type(args);
+                let mut params = v.generate_parameters(args);
+                let mut arguments = v.generate_arguments(args);
+                arguments.insert(0, quote! {&mut *self.lock().await});
+                if v.is_boxed_async(args) {
+                    params.insert(0, quote! {&'a self});
+                    let boxed = &self
+                        .config
+                        .box_path;
+                    let boxed_future = boxed_future(args, boxed, return_type);
+                    return quote! {
+                        fn #handler_name<'a>(#(#params),*) -> #boxed_future {
+                            #boxed::pin(async move { #mut_trait_name::#handler_name(#(#arguments),*).await })
+                        }
+                    };
+                }
+                params.insert(0, quote! {&self});
+                quote! {
+                    async fn #handler_name(#(#params),*) -> #return_type {
+                        #mut_trait_name::#handler_name(#(#arguments),*).await
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(quote! {
+            #async_trait
+            impl<T: #mut_trait_name #send_bound> #trait_name for #async_mutex<T> {
+                #output
+                #(#handlers)*
+            }
+        })
+    }
+
+    /// The handler methods of a sync lock, `lock` is the method that locks it for writing.
+    fn generate_lock_handlers(
+        &self,
+        args: &EnumHandlerArgs,
+        mut_trait_name: &Ident,
+        lock: TokenStream,
+    ) -> Vec<TokenStream> {
+        let crate_path = &self
+            .config
+            .crate_path;
+        self.handler_variants()
+            .map(|v| {
+                let handler_name = &v.handler_name;
+                let return_type = v.get_handler_return_type(args);
+                let mut params = v.generate_parameters(args);
+                params.insert(0, quote! {&self});
+                let mut arguments = v.generate_arguments(args);
+                arguments.insert(
+                    0,
+                    quote! {&mut *self.#lock().unwrap_or_else(#crate_path::__private::PoisonError::into_inner)},
+                );
+                quote! {
+                    fn #handler_name(#(#params),*) -> #return_type {
+                        #mut_trait_name::#handler_name(#(#arguments),*)
+                    }
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,97 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Ident, Path};
+
+use crate::{enum_handler_args::EnumHandlerArgs, model::Enum};
+
+impl Enum {
+    /// The `HandlerMethodInfo` of every handler method of the trait (`metadata = true`).
+    pub fn generate_handler_method_infos(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> Vec<TokenStream> {
+        let trait_name = self
+            .config
+            .trait_name
+            .to_string();
+        let has_default = args.is_default_implementation();
+        let crate_path = &self
+            .config
+            .crate_path;
+        self.variants
+            .iter()
+            .map(|v| {
+                let variant = v
+                    .name
+                    .to_string();
+                let method = v
+                    .handler_name
+                    .to_string();
+                let fields = v
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (name, ty))| {
+                        let name = v
+                            .parameter_name(name, i)
+                            .to_string();
+                        let ty = type_to_string(ty);
+                        quote! {
+                            #crate_path::HandlerFieldInfo {
+                                name: #name,
+                                ty: #ty,
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                quote! {
+                    #crate_path::HandlerMethodInfo {
+                        trait_name: #trait_name,
+                        variant: #variant,
+                        method: #method,
+                        fields: &[#(#fields),*],
+                        has_default: #has_default,
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// `impl EnumHandlerMeta for Enum` with the handler methods of all traits generated with `metadata = true`.
+pub fn generate_meta_impl(
+    enum_name: &Ident,
+    crate_path: &Path,
+    handler_method_infos: &[TokenStream],
+) -> TokenStream {
+    quote! {
+        impl #crate_path::EnumHandlerMeta for #enum_name {
+            const HANDLER_METHODS: &'static [#crate_path::HandlerMethodInfo] = &[#(#handler_method_infos),*];
+        }
+    }
+}
+
+/// The type as written in the enum, without the spaces between the tokens, eg. `Vec<i32>` instead of `Vec < i32 >`.
+pub(crate) fn type_to_string(ty: &impl ToTokens) -> String {
+    let mut output = ty
+        .to_token_stream()
+        .to_string();
+    for (from, to) in [
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (" ;", ";"),
+        (" :: ", "::"),
+        (" : ", ": "),
+        (":: ", "::"),
+        ("& ", "&"),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+    ] {
+        output = output.replace(from, to);
+    }
+    output
+}
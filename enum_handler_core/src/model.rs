@@ -1,12 +1,21 @@
-use crate::{enum_handler_args::EnumHandlerArgs, Error, Result};
+use crate::{
+    config::Config,
+    enum_handler_args::{EnumHandlerArgs, EnumHandlerVariantArgs},
+    Error, Result,
+};
 use heck::ToSnakeCase;
 use quote::format_ident;
-use syn::{Data, DeriveInput, Fields, Ident, Type, Variant, Visibility};
+use syn::{Data, DeriveInput, Fields, Ident, Path, Type, TypePath, Variant, Visibility};
 
 pub struct Enum {
     pub vis: Visibility,
     pub name: Ident,
+    /// The type the handler dispatches on: the enum itself or, for mirror enums, the `remote` enum.
+    pub path: Path,
     pub variants: Vec<EnumVariant>,
+    /// The handler of the unknown variants of a `#[non_exhaustive]` enum (`non_exhaustive = true`).
+    pub unknown_variant: Option<EnumVariant>,
+    pub config: Config,
 }
 
 impl Enum {
@@ -25,20 +34,95 @@ impl Enum {
         let name = derive_input
             .ident
             .clone();
-        let variants: Result<Vec<EnumVariant>> = data_enum
+        let path = match args.get_remote()? {
+            Some(remote) => remote,
+            None => Path::from(name.clone()),
+        };
+        let config = Config::new(args, &name, &vis)?;
+        let variants = data_enum
             .variants
             .iter()
-            .map(|v| EnumVariant::new(v, name.clone(), args))
-            .collect();
+            .map(|v| EnumVariant::new(v, path.clone(), &config))
+            .collect::<Result<Vec<_>>>()?;
+        for v in &variants {
+            if let Some(next) = v
+                .next
+                .iter()
+                .find(|next| {
+                    !variants
+                        .iter()
+                        .any(|other| other.name == **next)
+                })
+            {
+                return Err(Error::UnknownNextVariant(
+                    v.name
+                        .to_string(),
+                    next.to_string(),
+                ));
+            }
+        }
+        let unknown_variant = if args.is_non_exhaustive() {
+            let unknown_variant = EnumVariant::new_unknown(args, path.clone(), &config);
+            if variants
+                .iter()
+                .any(|v| v.name == unknown_variant.name)
+            {
+                return Err(Error::NonExhaustiveVariantConflict);
+            }
+            Some(unknown_variant)
+        } else {
+            None
+        };
+
+        Ok(Enum {
+            vis,
+            name,
+            path,
+            variants,
+            unknown_variant,
+            config,
+        })
+    }
+
+    /// The variants with a handler method, followed by the handler of unknown variants with `non_exhaustive`.
+    pub fn handler_variants(&self) -> impl Iterator<Item = &EnumVariant> {
+        self.variants
+            .iter()
+            .chain(&self.unknown_variant)
+    }
+
+    /// Variants with their own return types are wrapped in an output enum by the common handler method.
+    pub fn has_output_enum(&self) -> bool {
+        self.variants
+            .iter()
+            .any(|v| v.has_own_return_type)
+    }
 
-        match variants {
-            Ok(variants) => Ok(Enum {
-                vis,
-                name,
-                variants,
-            }),
-            Err(e) => Err(e),
+    /// The return type of the common handler method: the output enum or the common return type.
+    pub fn get_output_type(&self) -> Type {
+        if !self.has_output_enum() {
+            return self
+                .config
+                .return_type
+                .clone();
         }
+        Type::Path(TypePath {
+            qself: None,
+            path: Path::from(
+                self.config
+                    .output_name
+                    .clone(),
+            ),
+        })
+    }
+
+    pub fn has_async_variant(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> bool {
+        self.variants
+            .iter()
+            .any(|v| v.is_async(args))
     }
 }
 
@@ -46,21 +130,48 @@ pub enum EnumVariantType {
     Unit,
     Tuple,
     Struct,
+    /// The unknown variants of a `#[non_exhaustive]` enum, matched by `_`.
+    Unknown,
+}
+
+impl EnumVariantType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EnumVariantType::Unit => "unit",
+            EnumVariantType::Tuple => "tuple",
+            EnumVariantType::Struct => "struct",
+            EnumVariantType::Unknown => "unknown",
+        }
+    }
 }
 
+/// A variant with everything the trait, the mock and the helpers need, resolved once.
 pub struct EnumVariant {
-    pub enum_name: Ident,
+    pub enum_path: Path,
     pub name: Ident,
     pub variant_type: EnumVariantType,
+    /// The name of the handler method, eg. `on_set_value`.
+    pub handler_name: Ident,
     pub return_type: Type,
+    /// The return type of the mocked handler method, differs with `associated_output`.
+    pub mock_return_type: Type,
+    /// The return types wrapped in `Result<R, E>` with `error_type`.
+    pub handler_return_type: Type,
+    pub mock_handler_return_type: Type,
     pub fields: Vec<(Option<Ident>, Type)>,
+    /// Given by `#[enum_handler(async)]` or `#[enum_handler(sync)]`.
+    pub asyncness: Option<bool>,
+    /// The variant has its own return type, given by `#[enum_handler(returns = "Type")]`.
+    pub has_own_return_type: bool,
+    /// The variants that can follow this variant, given by `#[enum_handler(next = "A, B")]`.
+    pub next: Vec<Ident>,
 }
 
 impl EnumVariant {
     pub fn new(
         variant: &Variant,
-        enum_name: Ident,
-        args: &EnumHandlerArgs,
+        enum_path: Path,
+        config: &Config,
     ) -> Result<Self> {
         let name = variant
             .ident
@@ -78,32 +189,128 @@ impl EnumVariant {
                 (name, ty)
             })
             .collect();
-        let return_type = args.get_return_type()?;
+        let variant_args = EnumHandlerVariantArgs::from_variant(variant)?;
+        let has_own_return_type = variant_args
+            .returns
+            .is_some();
+        let (return_type, mock_return_type) = match variant_args.returns {
+            Some(returns) => (returns.clone(), returns),
+            None => (
+                config
+                    .return_type
+                    .clone(),
+                config
+                    .mock_return_type
+                    .clone(),
+            ),
+        };
+        let handler_name = format_ident!(
+            "{}_{}",
+            config.handler_name,
+            name.to_string()
+                .to_snake_case()
+        );
         let variant_type = match variant.fields {
             Fields::Unit => EnumVariantType::Unit,
             Fields::Named(_) => EnumVariantType::Struct,
             Fields::Unnamed(_) => EnumVariantType::Tuple,
         };
         Ok(EnumVariant {
-            enum_name,
+            enum_path,
             name,
             variant_type,
+            handler_name,
+            handler_return_type: config.get_fallible_return_type(return_type.clone()),
+            mock_handler_return_type: config.get_fallible_return_type(mock_return_type.clone()),
             return_type,
+            mock_return_type,
             fields,
+            asyncness: variant_args.asyncness,
+            has_own_return_type,
+            next: variant_args.next,
         })
     }
 
-    pub fn get_variant_handler_name(
+    /// The handler of unknown variants, it gets the whole event `e` (or only `pass_event`'s `e`).
+    fn new_unknown(
+        args: &EnumHandlerArgs,
+        enum_path: Path,
+        config: &Config,
+    ) -> Self {
+        let fields = if args.is_pass_event() {
+            Vec::new()
+        } else {
+            vec![(
+                Some(format_ident!("e")),
+                Type::Path(TypePath {
+                    qself: None,
+                    path: enum_path.clone(),
+                }),
+            )]
+        };
+        EnumVariant {
+            enum_path,
+            name: format_ident!("Unknown"),
+            variant_type: EnumVariantType::Unknown,
+            handler_name: format_ident!("{}_unknown", config.handler_name),
+            handler_return_type: config.get_fallible_return_type(
+                config
+                    .return_type
+                    .clone(),
+            ),
+            mock_handler_return_type: config.get_fallible_return_type(
+                config
+                    .mock_return_type
+                    .clone(),
+            ),
+            return_type: config
+                .return_type
+                .clone(),
+            mock_return_type: config
+                .mock_return_type
+                .clone(),
+            fields,
+            asyncness: None,
+            has_own_return_type: false,
+            next: Vec::new(),
+        }
+    }
+
+    /// The mock can't return `Self::Output`, it returns the concrete `mock_output` type instead.
+    pub fn get_return_type(
         &self,
         args: &EnumHandlerArgs,
-    ) -> Ident {
-        format_ident!(
-            "{}_{}",
-            args.get_handler_name(),
-            self.name
-                .to_string()
-                .to_snake_case()
-        )
+    ) -> &Type {
+        if args.is_mock() {
+            return &self.mock_return_type;
+        }
+        &self.return_type
+    }
+
+    /// The return type of the handler method, `Result<R, E>` with `error_type`.
+    pub fn get_handler_return_type(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> &Type {
+        if args.is_mock() {
+            return &self.mock_handler_return_type;
+        }
+        &self.handler_return_type
+    }
+
+    pub fn is_async(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> bool {
+        args.is_variant_async(self.asyncness)
+    }
+
+    /// The handler method returns a boxed future (`async_style = "boxed"`).
+    pub fn is_boxed_async(
+        &self,
+        args: &EnumHandlerArgs,
+    ) -> bool {
+        args.is_boxed_async() && self.is_async(args)
     }
 
     pub fn parameter_name(
@@ -122,7 +329,16 @@ impl EnumVariant {
         }
     }
 
-    fn is_single_field(&self) -> bool {
+    pub fn has_field_named(
+        &self,
+        field_name: &str,
+    ) -> bool {
+        self.fields
+            .iter()
+            .any(|(name, _)| matches!(name, Some(name) if name == field_name))
+    }
+
+    pub fn is_single_field(&self) -> bool {
         self.fields
             .len()
             == 1
@@ -1,3 +1,8 @@
+use std::{
+    env, process,
+    time::{Duration, UNIX_EPOCH},
+};
+
 use quote::quote;
 
 use crate::Error;
@@ -24,6 +29,24 @@ fn assert_tokens_eq(
         panic!("expected != actual");
     }
 }
+
+/// `async_trait`, or the re-export of `enum_handler` with the feature `async-trait`.
+fn async_trait_path() -> TokenStream {
+    if cfg!(feature = "async-trait") {
+        quote! { ::enum_handler::__private::async_trait }
+    } else {
+        quote! { async_trait }
+    }
+}
+
+/// `mockall`, or the re-export of `enum_handler` with the feature `mock`.
+fn mockall_path() -> TokenStream {
+    if cfg!(feature = "mock") {
+        quote! { ::enum_handler::__private::mockall }
+    } else {
+        quote! { mockall }
+    }
+}
 #[test]
 fn test_visibility_default_module() {
     let actual = enum_handler_core(quote! {
@@ -233,313 +256,587 @@ fn test_return_value() {
 }
 
 #[test]
-fn test_unit_variant() {
+fn test_associated_output() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        pub enum UnitVariant {
+        #[enum_handler(associated_output = true, output_bounds = "Send + 'static", mock_name = "MockHandler", mock_output = "i32")]
+        pub enum AssociatedOutput {
             Unit,
         }
     })
     .unwrap();
 
+    let mockall = mockall_path();
     let expected = quote! {
-        pub trait UnitVariantHandler {
-            fn on(&self, e: UnitVariant) -> () {
+        pub trait AssociatedOutputHandler {
+            type Output: Send + 'static;
+            fn on(&self, e: AssociatedOutput) -> Self::Output {
                 match (e) {
-                    UnitVariant::Unit => {
+                    AssociatedOutput::Unit => {
                         self.on_unit()
                     }
                 }
             }
-            fn on_unit(&self) -> ();
+            fn on_unit(&self) -> Self::Output;
+        }
+        #[cfg (test)]
+        #mockall::mock! {
+            pub MockHandler { }
+            impl AssociatedOutputHandler for MockHandler {
+                type Output = i32;
+                fn on_unit(&self) -> i32;
+            }
         }
     };
     assert_tokens_eq(&expected, &actual);
 }
 
 #[test]
-fn test_empty_tuple_variant() {
+fn test_associated_output_default_implementation() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        pub enum EmptyTupleVariant {
-            EmptyTuple(),
+        #[enum_handler(associated_output = true, default_implementation = true)]
+        pub enum AssociatedOutput {
+            Unit,
         }
     })
     .unwrap();
 
     let expected = quote! {
-        pub trait EmptyTupleVariantHandler {
-            fn on(&self, e: EmptyTupleVariant) -> () {
+        pub trait AssociatedOutputHandler {
+            type Output: ::core::default::Default;
+            fn on(&self, e: AssociatedOutput) -> Self::Output {
                 match (e) {
-                    EmptyTupleVariant::EmptyTuple() => {
-                        self.on_empty_tuple()
+                    AssociatedOutput::Unit => {
+                        self.on_unit()
                     }
                 }
             }
-            fn on_empty_tuple(&self) -> ();
+            fn on_unit(&self) -> Self::Output {
+                ::core::default::Default::default()
+            }
         }
     };
     assert_tokens_eq(&expected, &actual);
 }
 
 #[test]
-fn test_one_tuple_variant() {
+fn test_panic_if_associated_output_with_return_type() {
+    assert_eq!(
+        enum_handler_core(quote! {
+            #[derive(EnumHandler)]
+            #[enum_handler(associated_output = true, return_type = "i32")]
+            enum AssociatedOutput {
+                Unit,
+            }
+        })
+        .unwrap_err()
+        .to_string(),
+        Error::AssociatedOutputWithReturnType.to_string(),
+    );
+}
+
+#[test]
+fn test_variant_returns() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        pub enum OneTupleVariant {
-            OneTuple(String),
+        pub enum Request {
+            #[enum_handler(returns = "i32")]
+            Get,
+            Set(i32),
         }
     })
     .unwrap();
 
     let expected = quote! {
-        pub trait OneTupleVariantHandler {
-            fn on(&self, e: OneTupleVariant) -> () {
+        pub trait RequestHandler {
+            fn on(&self, e: Request) -> RequestOutput {
                 match (e) {
-                    OneTupleVariant::OneTuple(arg) => {
-                        self.on_one_tuple(arg)
+                    Request::Get => {
+                        RequestOutput::Get(self.on_get())
+                    }
+                    Request::Set(arg) => {
+                        RequestOutput::Set(self.on_set(arg))
                     }
                 }
             }
-            fn on_one_tuple(&self, arg: String) -> ();
+            fn on_get(&self) -> i32;
+            fn on_set(&self, arg: i32) -> ();
+        }
+        #[doc = " The results of the handler methods, returned by [`RequestHandler::on`]."]
+        pub enum RequestOutput {
+            Get(i32),
+            Set(()),
         }
     };
     assert_tokens_eq(&expected, &actual);
 }
 
 #[test]
-fn test_two_tuple_variant() {
+fn test_variant_returns_default_implementation() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        pub enum TwoTupleVariant {
-            TwoTuple(String, i32),
+        #[enum_handler(default_implementation = true, default_return_value = "()")]
+        pub enum Request {
+            Ping,
+            #[enum_handler(returns = "u32")]
+            Count,
         }
     })
     .unwrap();
 
     let expected = quote! {
-        pub trait TwoTupleVariantHandler {
-            fn on(&self, e: TwoTupleVariant) -> () {
+        pub trait RequestHandler {
+            fn on(&self, e: Request) -> RequestOutput {
                 match (e) {
-                    TwoTupleVariant::TwoTuple(arg0, arg1) => {
-                        self.on_two_tuple(arg0, arg1)
+                    Request::Ping => {
+                        RequestOutput::Ping(self.on_ping())
+                    }
+                    Request::Count => {
+                        RequestOutput::Count(self.on_count())
                     }
                 }
             }
-            fn on_two_tuple(&self, arg0: String, arg1: i32) -> ();
+            fn on_ping(&self) -> () {}
+            fn on_count(&self) -> u32 {
+                ::core::default::Default::default()
+            }
+        }
+        #[doc = " The results of the handler methods, returned by [`RequestHandler::on`]."]
+        pub enum RequestOutput {
+            Ping(()),
+            Count(u32),
         }
     };
     assert_tokens_eq(&expected, &actual);
 }
 
 #[test]
-fn test_empty_struct_variant() {
+fn test_error_type() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        pub enum EmptyStructVariant {
-            EmptyStruct{ },
+        #[enum_handler(error_type = "StoreError", return_type = "i32", default_return_value = "0", default_implementation = true)]
+        pub enum Request {
+            Get,
+            Set(i32),
         }
     })
     .unwrap();
 
     let expected = quote! {
-        pub trait EmptyStructVariantHandler {
-            fn on(&self, e: EmptyStructVariant) -> () {
+        pub trait RequestHandler {
+            fn on(&self, e: Request) -> ::core::result::Result<i32, StoreError> {
                 match (e) {
-                    EmptyStructVariant::EmptyStruct {} => {
-                        self.on_empty_struct()
+                    Request::Get => {
+                        self.on_get()
+                    }
+                    Request::Set(arg) => {
+                        self.on_set(arg)
                     }
                 }
             }
-            fn on_empty_struct(&self) -> ();
+            fn try_on(&self, e: Request) -> ::core::result::Result<i32, RequestHandlerError> {
+                match (e) {
+                    Request::Get => {
+                        self.on_get().map_err(|source| RequestHandlerError {
+                            variant: "Get",
+                            source,
+                        })
+                    }
+                    Request::Set(arg) => {
+                        self.on_set(arg).map_err(|source| RequestHandlerError {
+                            variant: "Set",
+                            source,
+                        })
+                    }
+                }
+            }
+            fn on_get(&self) -> ::core::result::Result<i32, StoreError> {
+                ::core::result::Result::Ok(0)
+            }
+            fn on_set(&self, arg: i32) -> ::core::result::Result<i32, StoreError> {
+                ::core::result::Result::Ok(0)
+            }
+        }
+        #[doc = " The error returned by [`RequestHandler::try_on`], the handler method of `variant` failed with `source`."]
+        #[derive(Debug)]
+        pub struct RequestHandlerError<E = StoreError> {
+            pub variant: &'static str,
+            pub source: E,
+        }
+        impl<E> ::core::fmt::Display for RequestHandlerError<E> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::write!(f, "the handler of {} failed", self.variant)
+            }
+        }
+        impl<E: ::core::error::Error + 'static> ::core::error::Error for RequestHandlerError<E> {
+            fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+                ::core::option::Option::Some(&self.source)
+            }
         }
     };
     assert_tokens_eq(&expected, &actual);
 }
 
 #[test]
-fn test_one_struct_variant() {
+fn test_accessors_and_kind() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        pub enum OneStructVariant {
-            OneStruct{ var1: String },
+        #[enum_handler(accessors = true, kind = "RequestKind")]
+        pub enum Request {
+            Get,
+            Set(i32),
         }
     })
     .unwrap();
 
     let expected = quote! {
-        pub trait OneStructVariantHandler {
-            fn on(&self, e: OneStructVariant) -> () {
+        pub trait RequestHandler {
+            fn on(&self, e: Request) -> () {
                 match (e) {
-                    OneStructVariant::OneStruct { var1 } => {
-                        self.on_one_struct(var1)
+                    Request::Get => {
+                        self.on_get()
+                    }
+                    Request::Set(arg) => {
+                        self.on_set(arg)
                     }
                 }
             }
-            fn on_one_struct(&self, var1: String) -> ();
+            fn on_get(&self) -> ();
+            fn on_set(&self, arg: i32) -> ();
+        }
+        impl Request {
+            pub fn is_get(&self) -> bool {
+                ::core::matches!(self, Self::Get)
+            }
+            pub fn is_set(&self) -> bool {
+                ::core::matches!(self, Self::Set(..))
+            }
+            #[allow(unreachable_patterns)]
+            pub fn as_set(&self) -> ::core::option::Option<&i32> {
+                match self {
+                    Self::Set(arg) => ::core::option::Option::Some(arg),
+                    _ => ::core::option::Option::None,
+                }
+            }
+            #[allow(unreachable_patterns)]
+            pub fn into_set(self) -> ::core::option::Option<i32> {
+                match self {
+                    Self::Set(arg) => ::core::option::Option::Some(arg),
+                    _ => ::core::option::Option::None,
+                }
+            }
+            #[doc = r" The name of the variant."]
+            pub fn variant_name(&self) -> &'static str {
+                match self {
+                    Self::Get => "Get",
+                    Self::Set(..) => "Set",
+                }
+            }
+        }
+        #[doc = " The variants of [`Request`] without their fields."]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum RequestKind {
+            Get,
+            Set,
+        }
+        impl RequestKind {
+            #[doc = r" All variants in declaration order."]
+            pub const ALL: [RequestKind; 2usize] = [RequestKind::Get, RequestKind::Set];
+            #[doc = r" The name of the variant."]
+            pub fn name(&self) -> &'static str {
+                match self {
+                    RequestKind::Get => "Get",
+                    RequestKind::Set => "Set",
+                }
+            }
+        }
+        impl ::core::fmt::Display for RequestKind {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+        impl ::core::str::FromStr for RequestKind {
+            type Err = ::enum_handler::ParseKindError;
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                match s {
+                    "Get" => ::core::result::Result::Ok(RequestKind::Get),
+                    "Set" => ::core::result::Result::Ok(RequestKind::Set),
+                    _ => ::core::result::Result::Err(::enum_handler::ParseKindError { kind: "RequestKind" }),
+                }
+            }
+        }
+        impl ::core::convert::From<&Request> for RequestKind {
+            fn from(e: &Request) -> Self {
+                e.kind()
+            }
+        }
+        impl Request {
+            #[doc = r" The variant without its fields."]
+            pub fn kind(&self) -> RequestKind {
+                match self {
+                    Self::Get => RequestKind::Get,
+                    Self::Set(..) => RequestKind::Set,
+                }
+            }
         }
     };
     assert_tokens_eq(&expected, &actual);
 }
 
 #[test]
-fn test_two_struct_variant() {
+fn test_metadata() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        pub enum TwoStructVariant {
-            TwoStruct{ var1: String, var2: i32 },
+        #[enum_handler(metadata = true, default_implementation = true)]
+        pub enum Request {
+            Get,
+            Set { key: String, values: Vec<i32> },
         }
     })
     .unwrap();
 
     let expected = quote! {
-        pub trait TwoStructVariantHandler {
-            fn on(&self, e: TwoStructVariant) -> () {
+        pub trait RequestHandler {
+            fn on(&self, e: Request) -> () {
                 match (e) {
-                    TwoStructVariant::TwoStruct { var1, var2 } => {
-                        self.on_two_struct(var1, var2)
+                    Request::Get => {
+                        self.on_get()
+                    }
+                    Request::Set { key, values } => {
+                        self.on_set(key, values)
                     }
                 }
             }
-            fn on_two_struct(&self, var1: String, var2: i32) -> ();
+            fn on_get(&self) -> () {}
+            fn on_set(&self, key: String, values: Vec<i32>) -> () {}
+        }
+        impl ::enum_handler::EnumHandlerMeta for Request {
+            const HANDLER_METHODS: &'static [::enum_handler::HandlerMethodInfo] = &[
+                ::enum_handler::HandlerMethodInfo {
+                    trait_name: "RequestHandler",
+                    variant: "Get",
+                    method: "on_get",
+                    fields: &[],
+                    has_default: true,
+                },
+                ::enum_handler::HandlerMethodInfo {
+                    trait_name: "RequestHandler",
+                    variant: "Set",
+                    method: "on_set",
+                    fields: &[
+                        ::enum_handler::HandlerFieldInfo {
+                            name: "key",
+                            ty: "String",
+                        },
+                        ::enum_handler::HandlerFieldInfo {
+                            name: "values",
+                            ty: "Vec<i32>",
+                        }
+                    ],
+                    has_default: true,
+                }
+            ];
         }
     };
     assert_tokens_eq(&expected, &actual);
 }
 
 #[test]
-fn test_all_variants() {
+fn test_callback() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        pub enum AllVariants {
-            Unit,
-            EmptyTuple(),
-            OneTuple(String),
-            TwoTuple(String, i32),
-            EmptyStruct {},
-            OneStruct { var1: String },
-            TwoStruct { var1: String, var2: i32 },
+        #[enum_handler(callback = my_crate::routes, default_implementation = true)]
+        pub enum Request {
+            Get,
+            Set { key: String, values: Vec<i32> },
+            Delete(String),
         }
     })
     .unwrap();
+
     let expected = quote! {
-        pub trait AllVariantsHandler {
-            fn on(&self, e: AllVariants) -> () {
+        pub trait RequestHandler {
+            fn on(&self, e: Request) -> () {
                 match (e) {
-                    AllVariants::Unit => {
-                        self.on_unit()
-                    }
-                    AllVariants::EmptyTuple() => {
-                        self.on_empty_tuple()
-                    }
-                    AllVariants::OneTuple(arg) => {
-                        self.on_one_tuple(arg)
-                    }
-                    AllVariants::TwoTuple(arg0, arg1) => {
-                        self.on_two_tuple(arg0, arg1)
-                    }
-                    AllVariants::EmptyStruct {} => {
-                        self.on_empty_struct()
+                    Request::Get => {
+                        self.on_get()
                     }
-                    AllVariants::OneStruct { var1 } => {
-                        self.on_one_struct(var1)
+                    Request::Set { key, values } => {
+                        self.on_set(key, values)
                     }
-                    AllVariants::TwoStruct { var1, var2 } => {
-                        self.on_two_struct(var1, var2)
+                    Request::Delete(arg) => {
+                        self.on_delete(arg)
                     }
                 }
             }
-            fn on_unit(&self) -> ();
-            fn on_empty_tuple(&self) -> ();
-            fn on_one_tuple(&self, arg: String) -> ();
-            fn on_two_tuple(&self, arg0: String, arg1: i32) -> ();
-            fn on_empty_struct(&self) -> ();
-            fn on_one_struct(&self, var1: String) -> ();
-            fn on_two_struct(&self, var1: String, var2: i32) -> ();
+            fn on_get(&self) -> () {}
+            fn on_set(&self, key: String, values: Vec<i32>) -> () {}
+            fn on_delete(&self, arg: String) -> () {}
+        }
+        my_crate::routes! {
+            enum = Request,
+            trait = RequestHandler,
+            variants = [
+                (Get, on_get, []),
+                (Set, on_set, [key: String, values: Vec<i32>]),
+                (Delete, on_delete, [arg: String])
+            ]
         }
     };
     assert_tokens_eq(&expected, &actual);
 }
 
 #[test]
-fn test_async() {
+fn test_blanket_impls() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        #[enum_handler(is_async = true, no_async_trait_macro = true)]
-        enum Async {
-            OneTuple(String),
+        #[enum_handler(blanket_impls = true, return_type = "i32", default_return_value = "0", pass_args_by_ref = true)]
+        pub enum Request {
+            Get,
+            Set(i32),
         }
     })
     .unwrap();
 
+    let handlers = quote! {
+        fn on_get(&self) -> i32 {
+            RequestHandler::on_get(&**self)
+        }
+        fn on_set(&self, arg: &i32) -> i32 {
+            RequestHandler::on_set(&**self, arg)
+        }
+    };
     let expected = quote! {
-        trait AsyncHandler {
-            async fn on(&self, e: Async) -> () {
+        pub trait RequestHandler {
+            fn on(&self, e: &Request) -> i32 {
                 match (e) {
-                    Async::OneTuple(arg) => {
-                        self.on_one_tuple(arg).await
+                    Request::Get => {
+                        self.on_get()
+                    }
+                    Request::Set(arg) => {
+                        self.on_set(arg)
                     }
                 }
             }
-            async fn on_one_tuple(&self, arg: String) -> ();
+            fn on_get(&self) -> i32;
+            fn on_set(&self, arg: &i32) -> i32;
+        }
+        impl<T: RequestHandler + ?::core::marker::Sized> RequestHandler for &T {
+            #handlers
+        }
+        impl<T: RequestHandler + ?::core::marker::Sized> RequestHandler for &mut T {
+            #handlers
+        }
+        impl<T: RequestHandler + ?::core::marker::Sized> RequestHandler for ::enum_handler::__private::Box<T> {
+            #handlers
+        }
+        impl<T: RequestHandler + ?::core::marker::Sized> RequestHandler for ::enum_handler::__private::Rc<T> {
+            #handlers
+        }
+        #[cfg(target_has_atomic = "ptr")]
+        impl<T: RequestHandler + ?::core::marker::Sized> RequestHandler for ::enum_handler::__private::Arc<T> {
+            #handlers
+        }
+        impl<T: RequestHandler> RequestHandler for ::core::option::Option<T> {
+            fn on_get(&self) -> i32 {
+                match self {
+                    ::core::option::Option::Some(inner) => RequestHandler::on_get(inner),
+                    ::core::option::Option::None => 0,
+                }
+            }
+            fn on_set(&self, arg: &i32) -> i32 {
+                match self {
+                    ::core::option::Option::Some(inner) => RequestHandler::on_set(inner, arg),
+                    ::core::option::Option::None => 0,
+                }
+            }
         }
     };
     assert_tokens_eq(&expected, &actual);
 }
 
 #[test]
-fn test_pass_args_by_ref() {
+fn test_lock_impls() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        #[enum_handler(pass_args_by_ref = true)]
-        pub enum OneTupleVariant {
-            OneTuple(String),
+        #[enum_handler(lock_impls = true, pass_args_by_ref = true)]
+        pub enum Request {
+            Set(i32),
         }
     })
     .unwrap();
 
     let expected = quote! {
-        pub trait OneTupleVariantHandler {
-            fn on(&self, e: &OneTupleVariant) -> () {
+        pub trait RequestHandler {
+            fn on(&self, e: &Request) -> () {
                 match (e) {
-                    OneTupleVariant::OneTuple(arg) => {
-                        self.on_one_tuple(arg)
+                    Request::Set(arg) => {
+                        self.on_set(arg)
                     }
                 }
             }
-            fn on_one_tuple(&self, arg: &str) -> ();
+            fn on_set(&self, arg: &i32) -> ();
+        }
+        pub trait RequestHandlerMut {
+            fn on(&mut self, e: &Request) -> () {
+                match (e) {
+                    Request::Set(arg) => {
+                        self.on_set(arg)
+                    }
+                }
+            }
+            fn on_set(&mut self, arg: &i32) -> ();
+        }
+        impl<T: RequestHandlerMut + ?::core::marker::Sized> RequestHandler for ::enum_handler::__private::Mutex<T> {
+            fn on_set(&self, arg: &i32) -> () {
+                RequestHandlerMut::on_set(&mut *self.lock().unwrap_or_else(::enum_handler::__private::PoisonError::into_inner), arg)
+            }
+        }
+        impl<T: RequestHandlerMut + ?::core::marker::Sized> RequestHandler for ::enum_handler::__private::RwLock<T> {
+            fn on_set(&self, arg: &i32) -> () {
+                RequestHandlerMut::on_set(&mut *self.write().unwrap_or_else(::enum_handler::__private::PoisonError::into_inner), arg)
+            }
         }
     };
     assert_tokens_eq(&expected, &actual);
 }
 
 #[test]
-fn test_mock() {
+fn test_lock_impls_async_mutex() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        #[enum_handler(mock_name = "MockHandler")]
-        enum Event {
-            OneTuple(String),
+        #[enum_handler(lock_impls = true, async_mutex = tokio::sync::Mutex, is_async = true, no_async_trait_macro = true)]
+        pub enum Request {
+            Get,
         }
     })
     .unwrap();
 
     let expected = quote! {
-        trait EventHandler {
-            fn on(&self, e: Event) -> () {
+        pub trait RequestHandler {
+            async fn on(&self, e: Request) -> () {
                 match (e) {
-                    Event::OneTuple(arg) => {
-                        self.on_one_tuple(arg)
+                    Request::Get => {
+                        self.on_get().await
                     }
                 }
             }
-            fn on_one_tuple(&self, arg: String) -> ();
+            async fn on_get(&self) -> ();
         }
-        #[cfg (test)]
-        mockall::mock! {
-            MockHandler { }
-            impl EventHandler for MockHandler {
-                fn on_one_tuple(&self, arg: String) -> ();
+        pub trait RequestHandlerMut {
+            async fn on(&mut self, e: Request) -> () {
+                match (e) {
+                    Request::Get => {
+                        self.on_get().await
+                    }
+                }
+            }
+            async fn on_get(&mut self) -> ();
+        }
+        impl<T: RequestHandlerMut> RequestHandler for tokio::sync::Mutex<T> {
+            async fn on_get(&self) -> () {
+                RequestHandlerMut::on_get(&mut *self.lock().await).await
             }
         }
     };
@@ -547,18 +844,1132 @@ fn test_mock() {
 }
 
 #[test]
-fn test_async_mock() {
+fn test_lock_impls_require_async_mutex() {
+    assert_eq!(
+        enum_handler_core(quote! {
+            #[derive(EnumHandler)]
+            #[enum_handler(lock_impls = true, is_async = true)]
+            enum Request {
+                Get,
+            }
+        })
+        .unwrap_err()
+        .to_string(),
+        Error::LockImplsRequireAsyncMutex.to_string(),
+    );
+}
+
+#[test]
+fn test_lock_impls_with_mixed_async() {
+    assert_eq!(
+        enum_handler_core(quote! {
+            #[derive(EnumHandler)]
+            #[enum_handler(lock_impls = true, async_mutex = "tokio::sync::Mutex")]
+            enum Request {
+                Get,
+                #[enum_handler(async)]
+                Load,
+            }
+        })
+        .unwrap_err()
+        .to_string(),
+        Error::LockImplsWithMixedAsync.to_string(),
+    );
+}
+
+#[test]
+fn test_crate_path() {
     let actual = enum_handler_core(quote! {
         #[derive(EnumHandler)]
-        #[enum_handler(mock_name = "MockHandler", is_async = true)]
+        #[enum_handler(crate = "my_facade::enum_handler", is_async = true, metadata = true)]
+        pub enum Request {
+            Get,
+        }
+    })
+    .unwrap();
+
+    let async_trait = if cfg!(feature = "async-trait") {
+        quote! { #[my_facade::enum_handler::__private::async_trait::async_trait] }
+    } else {
+        quote! { #[async_trait::async_trait] }
+    };
+    let expected = quote! {
+        #async_trait
+        pub trait RequestHandler {
+            async fn on(&self, e: Request) -> () {
+                match (e) {
+                    Request::Get => {
+                        self.on_get().await
+                    }
+                }
+            }
+            async fn on_get(&self) -> ();
+        }
+        impl my_facade::enum_handler::EnumHandlerMeta for Request {
+            const HANDLER_METHODS: &'static [my_facade::enum_handler::HandlerMethodInfo] = &[
+                my_facade::enum_handler::HandlerMethodInfo {
+                    trait_name: "RequestHandler",
+                    variant: "Get",
+                    method: "on_get",
+                    fields: &[],
+                    has_default: false,
+                }
+            ];
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_export_files() {
+    let derive_input = syn::parse2::<DeriveInput>(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(flavours = "both")]
+        pub enum ExportEvent {
+            Reset,
+            Set(Option<u8>),
+            Move { from: i32, to: i32 },
+        }
+    })
+    .unwrap();
+    let args_list = EnumHandlerArgs::list_from_derive_input(&derive_input).unwrap();
+    let traits = Traits::new(&derive_input, args_list).unwrap();
+    let export_dir = env::temp_dir().join(format!("enum_handler_export_{}", process::id()));
+
+    export::write_export_files_to(
+        &export_dir,
+        &derive_input,
+        &traits.flavour_args_list,
+        &traits.enums,
+    )
+    .unwrap();
+
+    let read_json = |file_name: &str| -> serde_json::Value { serde_json::from_str(&std::fs::read_to_string(export_dir.join(file_name)).unwrap()).unwrap() };
+    let crate_name = debug::crate_name();
+    let interface = read_json(&format!("{}.ExportEvent.json", crate_name));
+    let schema = read_json(&format!("{}.ExportEvent.schema.json", crate_name));
+    std::fs::remove_dir_all(&export_dir).unwrap();
+
+    assert_eq!(interface["variants"][1]["fields"][0]["type"], "Option<u8>");
+    assert_eq!(interface["traits"][0]["name"], "ExportEventHandler");
+    assert_eq!(interface["traits"][1]["name"], "AsyncExportEventHandler");
+    assert_eq!(interface["traits"][1]["is_async"], true);
+    assert_eq!(interface["traits"][0]["methods"][2]["name"], "on_move");
+    assert_eq!(
+        interface["traits"][0]["methods"][2]["parameters"][1]["name"],
+        "to"
+    );
+    assert_eq!(interface["traits"][0]["methods"][2]["return_type"], "()");
+
+    assert_eq!(schema["oneOf"][0], serde_json::json!({ "const": "Reset" }));
+    assert_eq!(
+        schema["oneOf"][1]["properties"]["Set"],
+        serde_json::json!({ "anyOf": [{ "type": "integer", "minimum": 0 }, { "type": "null" }] })
+    );
+    assert_eq!(
+        schema["oneOf"][2]["properties"]["Move"]["required"],
+        serde_json::json!(["from", "to"])
+    );
+}
+
+#[test]
+fn test_export_serde_schema() {
+    let export_schema = |tokens: TokenStream| {
+        let derive_input = syn::parse2::<DeriveInput>(tokens).unwrap();
+        let args_list = EnumHandlerArgs::list_from_derive_input(&derive_input).unwrap();
+        let export_dir = env::temp_dir().join(format!(
+            "enum_handler_export_serde_{}_{}",
+            process::id(),
+            derive_input.ident
+        ));
+        let traits = Traits::new(&derive_input, args_list).unwrap();
+        export::write_export_files_to(
+            &export_dir,
+            &derive_input,
+            &traits.flavour_args_list,
+            &traits.enums,
+        )
+        .unwrap();
+        let path = export_dir.join(format!(
+            "{}.{}.schema.json",
+            debug::crate_name(),
+            derive_input.ident
+        ));
+        let schema: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        std::fs::remove_dir_all(&export_dir).unwrap();
+        schema
+    };
+
+    let schema = export_schema(quote! {
+        #[derive(EnumHandler, Serialize)]
+        #[serde(rename_all = "snake_case", tag = "type")]
+        pub enum Renamed {
+            ResetAll,
+            #[serde(rename = "move", rename_all = "camelCase")]
+            MoveTo { from_x: i32, #[serde(rename = "y")] from_y: i32, #[serde(skip)] cached: bool },
+            #[serde(skip)]
+            Internal(u8),
+            Wrapped(Inner),
+        }
+    });
+    assert_eq!(
+        schema["oneOf"][0],
+        serde_json::json!({
+            "type": "object",
+            "properties": { "type": { "const": "reset_all" } },
+            "required": ["type"],
+            "additionalProperties": false,
+        })
+    );
+    assert_eq!(
+        schema["oneOf"][1],
+        serde_json::json!({
+            "type": "object",
+            "properties": { "type": { "const": "move" }, "fromX": { "type": "integer" }, "y": { "type": "integer" } },
+            "required": ["type", "fromX", "y"],
+            "additionalProperties": false,
+        })
+    );
+    assert_eq!(
+        schema["oneOf"][2]["allOf"][0]["properties"]["type"],
+        serde_json::json!({ "const": "wrapped" })
+    );
+    assert_eq!(
+        schema["oneOf"][2]["allOf"][1],
+        serde_json::json!({ "title": "Inner" })
+    );
+    assert_eq!(
+        schema["oneOf"]
+            .as_array()
+            .unwrap()
+            .len(),
+        3
+    );
+
+    let schema = export_schema(quote! {
+        #[derive(EnumHandler, Serialize)]
+        #[serde(tag = "t", content = "c")]
+        pub enum Adjacent {
+            Off,
+            Dim(u8),
+        }
+    });
+    assert_eq!(schema["oneOf"][0]["required"], serde_json::json!(["t"]));
+    assert_eq!(
+        schema["oneOf"][1]["required"],
+        serde_json::json!(["c", "t"])
+    );
+    assert_eq!(
+        schema["oneOf"][1]["properties"]["c"],
+        serde_json::json!({ "type": "integer", "minimum": 0 })
+    );
+
+    let schema = export_schema(quote! {
+        #[derive(EnumHandler, Serialize)]
+        #[serde(untagged)]
+        pub enum Untagged {
+            Empty,
+            Number(f64),
+        }
+    });
+    assert_eq!(
+        schema["oneOf"],
+        serde_json::json!([{ "type": "null" }, { "type": "number" }])
+    );
+}
+
+#[test]
+fn test_diagram_files() {
+    let derive_input = syn::parse2::<DeriveInput>(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(return_type = "Option<bool>")]
+        pub enum Light {
+            #[enum_handler(next = "Dim")]
+            Off,
+            #[enum_handler(next = "Off, Dim")]
+            Dim(u8),
+        }
+    })
+    .unwrap();
+    let args_list = EnumHandlerArgs::list_from_derive_input(&derive_input).unwrap();
+    let traits = Traits::new(&derive_input, args_list).unwrap();
+    let diagram_dir = env::temp_dir().join(format!("enum_handler_diagram_{}", process::id()));
+
+    diagram::write_diagram_files_to(
+        &diagram_dir,
+        &derive_input,
+        &traits.flavour_args_list,
+        &traits.enums,
+    )
+    .unwrap();
+
+    let crate_name = debug::crate_name();
+    let mermaid = std::fs::read_to_string(diagram_dir.join(format!("{}.Light.mmd", crate_name))).unwrap();
+    let dot = std::fs::read_to_string(diagram_dir.join(format!("{}.Light.dot", crate_name))).unwrap();
+    std::fs::remove_dir_all(&diagram_dir).unwrap();
+
+    assert_eq!(
+        mermaid,
+        r#"flowchart LR
+    Light[["enum Light"]]
+    Light_Off(["Off"])
+    Light_Dim(["Dim(u8)"])
+    LightHandler{{"trait LightHandler"}}
+    LightHandler_on_off["on_off() -#gt; Option#lt;bool#gt;"]
+    LightHandler_on_dim["on_dim(arg: u8) -#gt; Option#lt;bool#gt;"]
+    Light --> Light_Off
+    Light --> Light_Dim
+    Light_Off ==> Light_Dim
+    Light_Dim ==> Light_Off
+    Light_Dim ==> Light_Dim
+    LightHandler --> LightHandler_on_off
+    Light_Off -.-> LightHandler_on_off
+    LightHandler --> LightHandler_on_dim
+    Light_Dim -.-> LightHandler_on_dim
+"#
+    );
+    assert_eq!(
+        dot,
+        r#"digraph Light {
+    rankdir=LR;
+    Light [shape=box3d, label="enum Light"];
+    Light_Off [shape=ellipse, label="Off"];
+    Light_Dim [shape=ellipse, label="Dim(u8)"];
+    LightHandler [shape=component, label="trait LightHandler"];
+    LightHandler_on_off [shape=box, label="on_off() -> Option<bool>"];
+    LightHandler_on_dim [shape=box, label="on_dim(arg: u8) -> Option<bool>"];
+    Light -> Light_Off;
+    Light -> Light_Dim;
+    Light_Off -> Light_Dim [style=bold];
+    Light_Dim -> Light_Off [style=bold];
+    Light_Dim -> Light_Dim [style=bold];
+    LightHandler -> LightHandler_on_off;
+    Light_Off -> LightHandler_on_off [style=dashed];
+    LightHandler -> LightHandler_on_dim;
+    Light_Dim -> LightHandler_on_dim [style=dashed];
+}
+"#
+    );
+}
+
+#[test]
+fn test_panic_if_unknown_next_variant() {
+    assert_eq!(
+        enum_handler_core(quote! {
+            #[derive(EnumHandler)]
+            enum UnknownNext {
+                #[enum_handler(next = "Stop")]
+                Start,
+                Stopped,
+            }
+        })
+        .unwrap_err()
+        .to_string(),
+        Error::UnknownNextVariant("Start".to_string(), "Stop".to_string()).to_string(),
+    );
+}
+
+#[test]
+fn test_debug_filter() {
+    assert!(debug::matches_filter("", "CounterEvent"));
+    assert!(debug::matches_filter("CounterEvent", "CounterEvent"));
+    assert!(debug::matches_filter("Store*, Counter*", "CounterEvent"));
+    assert!(debug::matches_filter("*Event", "CounterEvent"));
+    assert!(debug::matches_filter("Counter?vent", "CounterEvent"));
+    assert!(!debug::matches_filter("Counter", "CounterEvent"));
+    assert!(!debug::matches_filter("Store*", "CounterEvent"));
+}
+
+#[test]
+fn test_debug_timestamp() {
+    assert_eq!(debug::utc_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+    assert_eq!(
+        debug::utc_timestamp(UNIX_EPOCH + Duration::from_secs(1_724_198_400 + 3_661)),
+        "2024-08-21T01:01:01Z"
+    );
+    assert_eq!(
+        debug::utc_timestamp(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+        "2000-02-29T00:00:00Z"
+    );
+}
+
+#[test]
+fn test_debug_format_tokens() {
+    let formatted = debug::format_tokens(&quote! {
+        pub trait AHandler { fn on_b(&self) -> (); }
+    });
+    assert_eq!(
+        formatted,
+        "pub trait AHandler {\n    fn on_b(&self) -> ();\n}\n"
+    );
+}
+
+#[test]
+fn test_unit_variant() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        pub enum UnitVariant {
+            Unit,
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait UnitVariantHandler {
+            fn on(&self, e: UnitVariant) -> () {
+                match (e) {
+                    UnitVariant::Unit => {
+                        self.on_unit()
+                    }
+                }
+            }
+            fn on_unit(&self) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_empty_tuple_variant() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        pub enum EmptyTupleVariant {
+            EmptyTuple(),
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait EmptyTupleVariantHandler {
+            fn on(&self, e: EmptyTupleVariant) -> () {
+                match (e) {
+                    EmptyTupleVariant::EmptyTuple() => {
+                        self.on_empty_tuple()
+                    }
+                }
+            }
+            fn on_empty_tuple(&self) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_one_tuple_variant() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        pub enum OneTupleVariant {
+            OneTuple(String),
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait OneTupleVariantHandler {
+            fn on(&self, e: OneTupleVariant) -> () {
+                match (e) {
+                    OneTupleVariant::OneTuple(arg) => {
+                        self.on_one_tuple(arg)
+                    }
+                }
+            }
+            fn on_one_tuple(&self, arg: String) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_two_tuple_variant() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        pub enum TwoTupleVariant {
+            TwoTuple(String, i32),
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait TwoTupleVariantHandler {
+            fn on(&self, e: TwoTupleVariant) -> () {
+                match (e) {
+                    TwoTupleVariant::TwoTuple(arg0, arg1) => {
+                        self.on_two_tuple(arg0, arg1)
+                    }
+                }
+            }
+            fn on_two_tuple(&self, arg0: String, arg1: i32) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_empty_struct_variant() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        pub enum EmptyStructVariant {
+            EmptyStruct{ },
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait EmptyStructVariantHandler {
+            fn on(&self, e: EmptyStructVariant) -> () {
+                match (e) {
+                    EmptyStructVariant::EmptyStruct {} => {
+                        self.on_empty_struct()
+                    }
+                }
+            }
+            fn on_empty_struct(&self) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_one_struct_variant() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        pub enum OneStructVariant {
+            OneStruct{ var1: String },
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait OneStructVariantHandler {
+            fn on(&self, e: OneStructVariant) -> () {
+                match (e) {
+                    OneStructVariant::OneStruct { var1 } => {
+                        self.on_one_struct(var1)
+                    }
+                }
+            }
+            fn on_one_struct(&self, var1: String) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_two_struct_variant() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        pub enum TwoStructVariant {
+            TwoStruct{ var1: String, var2: i32 },
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait TwoStructVariantHandler {
+            fn on(&self, e: TwoStructVariant) -> () {
+                match (e) {
+                    TwoStructVariant::TwoStruct { var1, var2 } => {
+                        self.on_two_struct(var1, var2)
+                    }
+                }
+            }
+            fn on_two_struct(&self, var1: String, var2: i32) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_all_variants() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        pub enum AllVariants {
+            Unit,
+            EmptyTuple(),
+            OneTuple(String),
+            TwoTuple(String, i32),
+            EmptyStruct {},
+            OneStruct { var1: String },
+            TwoStruct { var1: String, var2: i32 },
+        }
+    })
+    .unwrap();
+    let expected = quote! {
+        pub trait AllVariantsHandler {
+            fn on(&self, e: AllVariants) -> () {
+                match (e) {
+                    AllVariants::Unit => {
+                        self.on_unit()
+                    }
+                    AllVariants::EmptyTuple() => {
+                        self.on_empty_tuple()
+                    }
+                    AllVariants::OneTuple(arg) => {
+                        self.on_one_tuple(arg)
+                    }
+                    AllVariants::TwoTuple(arg0, arg1) => {
+                        self.on_two_tuple(arg0, arg1)
+                    }
+                    AllVariants::EmptyStruct {} => {
+                        self.on_empty_struct()
+                    }
+                    AllVariants::OneStruct { var1 } => {
+                        self.on_one_struct(var1)
+                    }
+                    AllVariants::TwoStruct { var1, var2 } => {
+                        self.on_two_struct(var1, var2)
+                    }
+                }
+            }
+            fn on_unit(&self) -> ();
+            fn on_empty_tuple(&self) -> ();
+            fn on_one_tuple(&self, arg: String) -> ();
+            fn on_two_tuple(&self, arg0: String, arg1: i32) -> ();
+            fn on_empty_struct(&self) -> ();
+            fn on_one_struct(&self, var1: String) -> ();
+            fn on_two_struct(&self, var1: String, var2: i32) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_async() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(is_async = true, no_async_trait_macro = true)]
+        enum Async {
+            OneTuple(String),
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        trait AsyncHandler {
+            async fn on(&self, e: Async) -> () {
+                match (e) {
+                    Async::OneTuple(arg) => {
+                        self.on_one_tuple(arg).await
+                    }
+                }
+            }
+            async fn on_one_tuple(&self, arg: String) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_async_boxed() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(is_async = true, async_style = "boxed", pass_args_by_ref = true)]
+        enum Async {
+            OneTuple(String),
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        trait AsyncHandler: ::core::marker::Send + ::core::marker::Sync {
+            fn on<'a>(&'a self, e: &'a Async) -> ::core::pin::Pin<::enum_handler::__private::Box<dyn ::core::future::Future<Output = ()> + ::core::marker::Send + 'a>> {
+                ::enum_handler::__private::Box::pin(async move {
+                    match (e) {
+                        Async::OneTuple(arg) => {
+                            self.on_one_tuple(arg).await
+                        }
+                    }
+                })
+            }
+            fn on_one_tuple<'a>(&'a self, arg: &'a str) -> ::core::pin::Pin<::enum_handler::__private::Box<dyn ::core::future::Future<Output = ()> + ::core::marker::Send + 'a>>;
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_async_variant() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(no_async_trait_macro = true)]
+        enum Mixed {
+            Unit,
+            #[enum_handler(async)]
+            OneTuple(String),
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        trait MixedHandler {
+            async fn on(&self, e: Mixed) -> () {
+                match (e) {
+                    Mixed::Unit => {
+                        self.on_unit()
+                    }
+                    Mixed::OneTuple(arg) => {
+                        self.on_one_tuple(arg).await
+                    }
+                }
+            }
+            fn on_unit(&self) -> ();
+            async fn on_one_tuple(&self, arg: String) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_sync_variant() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(is_async = true, no_async_trait_macro = true)]
+        enum Mixed {
+            #[enum_handler(sync)]
+            Unit,
+            OneTuple(String),
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        trait MixedHandler {
+            async fn on(&self, e: Mixed) -> () {
+                match (e) {
+                    Mixed::Unit => {
+                        self.on_unit()
+                    }
+                    Mixed::OneTuple(arg) => {
+                        self.on_one_tuple(arg).await
+                    }
+                }
+            }
+            fn on_unit(&self) -> ();
+            async fn on_one_tuple(&self, arg: String) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_pass_args_by_ref() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(pass_args_by_ref = true)]
+        pub enum OneTupleVariant {
+            OneTuple(String),
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait OneTupleVariantHandler {
+            fn on(&self, e: &OneTupleVariant) -> () {
+                match (e) {
+                    OneTupleVariant::OneTuple(arg) => {
+                        self.on_one_tuple(arg)
+                    }
+                }
+            }
+            fn on_one_tuple(&self, arg: &str) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_pass_event() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(pass_args_by_ref = true, pass_event = true)]
+        pub enum PassEvent {
+            Unit,
+            OneStruct { var1: i32 },
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait PassEventHandler {
+            fn on(&self, e: &PassEvent) -> () {
+                match (e) {
+                    PassEvent::Unit => {
+                        self.on_unit(e)
+                    }
+                    PassEvent::OneStruct { var1 } => {
+                        self.on_one_struct(e, var1)
+                    }
+                }
+            }
+            fn on_unit(&self, e: &PassEvent) -> ();
+            fn on_one_struct(&self, e: &PassEvent, var1: &i32) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_pass_event_requires_args_by_ref() {
+    assert_eq!(
+        enum_handler_core(quote! {
+            #[derive(EnumHandler)]
+            #[enum_handler(pass_event = true)]
+            enum PassEvent {
+                Unit,
+            }
+        })
+        .unwrap_err()
+        .to_string(),
+        Error::PassEventRequiresArgsByRef.to_string(),
+    );
+}
+
+#[test]
+fn test_non_exhaustive_remote() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(remote = "other::Remote", non_exhaustive = true)]
+        pub enum Mirror {
+            Unit,
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait MirrorHandler {
+            fn on(&self, e: other::Remote) -> () {
+                match (e) {
+                    other::Remote::Unit => {
+                        self.on_unit()
+                    }
+                    #[allow(unreachable_patterns)]
+                    _ => {
+                        self.on_unknown(e)
+                    }
+                }
+            }
+            fn on_unit(&self) -> ();
+            fn on_unknown(&self, e: other::Remote) -> ();
+        }
+        const _: fn(other::Remote) -> Mirror = |e| match e {
+            other::Remote::Unit => Mirror::Unit,
+            #[allow(unreachable_patterns)]
+            _ => ::core::unreachable!(),
+        };
+        const _: fn(Mirror) -> other::Remote = |e| match e {
+            Mirror::Unit => other::Remote::Unit,
+        };
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_non_exhaustive_variant_conflict() {
+    assert_eq!(
+        enum_handler_core(quote! {
+            #[derive(EnumHandler)]
+            #[enum_handler(non_exhaustive = true)]
+            enum Request {
+                Unknown,
+            }
+        })
+        .unwrap_err()
+        .to_string(),
+        Error::NonExhaustiveVariantConflict.to_string(),
+    );
+}
+
+#[test]
+fn test_remote() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(remote = "other::Remote")]
+        pub enum Mirror {
+            Unit,
+            OneTuple(String),
+            OneStruct { var1: i32 },
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait MirrorHandler {
+            fn on(&self, e: other::Remote) -> () {
+                match (e) {
+                    other::Remote::Unit => {
+                        self.on_unit()
+                    }
+                    other::Remote::OneTuple(arg) => {
+                        self.on_one_tuple(arg)
+                    }
+                    other::Remote::OneStruct { var1 } => {
+                        self.on_one_struct(var1)
+                    }
+                }
+            }
+            fn on_unit(&self) -> ();
+            fn on_one_tuple(&self, arg: String) -> ();
+            fn on_one_struct(&self, var1: i32) -> ();
+        }
+        const _: fn(other::Remote) -> Mirror = |e| match e {
+            other::Remote::Unit => Mirror::Unit,
+            other::Remote::OneTuple(arg) => Mirror::OneTuple(arg),
+            other::Remote::OneStruct { var1 } => Mirror::OneStruct { var1 },
+        };
+        const _: fn(Mirror) -> other::Remote = |e| match e {
+            Mirror::Unit => other::Remote::Unit,
+            Mirror::OneTuple(arg) => other::Remote::OneTuple(arg),
+            Mirror::OneStruct { var1 } => other::Remote::OneStruct { var1 },
+        };
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_mock() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(mock_name = "MockHandler")]
+        enum Event {
+            OneTuple(String),
+        }
+    })
+    .unwrap();
+
+    let mockall = mockall_path();
+    let expected = quote! {
+        trait EventHandler {
+            fn on(&self, e: Event) -> () {
+                match (e) {
+                    Event::OneTuple(arg) => {
+                        self.on_one_tuple(arg)
+                    }
+                }
+            }
+            fn on_one_tuple(&self, arg: String) -> ();
+        }
+        #[cfg (test)]
+        #mockall::mock! {
+            MockHandler { }
+            impl EventHandler for MockHandler {
+                fn on_one_tuple(&self, arg: String) -> ();
+            }
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_async_mock() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(mock_name = "MockHandler", is_async = true)]
+        enum Event {
+            OneTuple(String),
+        }
+    })
+    .unwrap();
+
+    let async_trait = async_trait_path();
+    let mockall = mockall_path();
+    let expected = quote! {
+        #[#async_trait::async_trait]
+        trait EventHandler {
+            async fn on(&self, e: Event) -> () {
+                match (e) {
+                    Event::OneTuple(arg) => {
+                        self.on_one_tuple(arg).await
+                    }
+                }
+            }
+            async fn on_one_tuple(&self, arg: String) -> ();
+        }
+        #[cfg (test)]
+        #mockall::mock! {
+            MockHandler { }
+            #[#async_trait::async_trait]
+            impl EventHandler for MockHandler {
+                async fn on_one_tuple(&self, arg: String) -> ();
+            }
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_multiple_traits() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(trait_name = "UiHandler")]
+        #[enum_handler(trait_name = "StoreHandler", handler_name = "store", return_type = "bool")]
+        pub enum MultipleTraits {
+            Unit,
+        }
+    })
+    .unwrap();
+
+    let expected = quote! {
+        pub trait UiHandler {
+            fn on(&self, e: MultipleTraits) -> () {
+                match (e) {
+                    MultipleTraits::Unit => {
+                        self.on_unit()
+                    }
+                }
+            }
+            fn on_unit(&self) -> ();
+        }
+        pub trait StoreHandler {
+            fn store(&self, e: MultipleTraits) -> bool {
+                match (e) {
+                    MultipleTraits::Unit => {
+                        self.store_unit()
+                    }
+                }
+            }
+            fn store_unit(&self) -> bool;
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_panic_if_duplicate_trait_name() {
+    assert_eq!(
+        enum_handler_core(quote! {
+            #[derive(EnumHandler)]
+            #[enum_handler(return_type = "i32")]
+            #[enum_handler(is_async = true)]
+            enum DuplicateTraitName {
+                Unit,
+            }
+        })
+        .unwrap_err()
+        .to_string(),
+        Error::DuplicateTraitName("DuplicateTraitNameHandler".to_string()).to_string(),
+    );
+}
+
+#[test]
+fn test_flavours_both() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(flavours = "both")]
+        pub enum Flavours {
+            OneTuple(String),
+        }
+    })
+    .unwrap();
+
+    let async_trait = async_trait_path();
+    let expected = quote! {
+        pub trait FlavoursHandler {
+            fn on(&self, e: Flavours) -> () {
+                match (e) {
+                    Flavours::OneTuple(arg) => {
+                        self.on_one_tuple(arg)
+                    }
+                }
+            }
+            fn on_one_tuple(&self, arg: String) -> ();
+        }
+        #[#async_trait::async_trait]
+        pub trait AsyncFlavoursHandler {
+            async fn on(&self, e: Flavours) -> () {
+                match (e) {
+                    Flavours::OneTuple(arg) => {
+                        self.on_one_tuple(arg).await
+                    }
+                }
+            }
+            async fn on_one_tuple(&self, arg: String) -> ();
+        }
+        #[#async_trait::async_trait]
+        impl<T: FlavoursHandler + Sync + ?Sized> AsyncFlavoursHandler for T {
+            async fn on_one_tuple(&self, arg: String) -> () {
+                FlavoursHandler::on_one_tuple(self, arg)
+            }
+        }
+        #[doc = " Implements [`FlavoursHandler`] for the [`AsyncFlavoursHandler`] `T` by running every handler to completion on the current thread."]
+        pub struct BlockingFlavoursHandler<T>(pub T);
+        impl<T: AsyncFlavoursHandler + ::core::marker::Sync> FlavoursHandler for BlockingFlavoursHandler<T> {
+            fn on_one_tuple(&self, arg: String) -> () {
+                ::enum_handler::block_on(AsyncFlavoursHandler::on_one_tuple(&self.0, arg))
+            }
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_panic_if_invalid_flavours() {
+    assert_eq!(
+        enum_handler_core(quote! {
+            #[derive(EnumHandler)]
+            #[enum_handler(flavours = "all")]
+            enum InvalidFlavours {
+                Unit,
+            }
+        })
+        .unwrap_err()
+        .to_string(),
+        Error::InvalidFlavours("all".to_string()).to_string(),
+    );
+}
+
+#[test]
+fn test_attribute() {
+    let actual = enum_handler_attribute_core(
+        quote! { handler_name = "handle" },
+        quote! {
+            #[derive(Debug)]
+            pub enum Attribute {
+                #[enum_handler(sync)]
+                OneStruct {
+                    #[enum_handler(ignored)]
+                    var1: i32
+                },
+            }
+        },
+    )
+    .unwrap();
+
+    let expected = quote! {
+        #[derive(Debug)]
+        pub enum Attribute {
+            OneStruct {
+                var1: i32
+            },
+        }
+        pub trait AttributeHandler {
+            fn handle(&self, e: Attribute) -> () {
+                match (e) {
+                    Attribute::OneStruct { var1 } => {
+                        self.handle_one_struct(var1)
+                    }
+                }
+            }
+            fn handle_one_struct(&self, var1: i32) -> ();
+        }
+    };
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn test_async_mock_not_send() {
+    let actual = enum_handler_core(quote! {
+        #[derive(EnumHandler)]
+        #[enum_handler(mock_name = "MockHandler", is_async = true, async_send = false)]
         enum Event {
             OneTuple(String),
         }
     })
     .unwrap();
 
+    let async_trait = async_trait_path();
+    let mockall = mockall_path();
     let expected = quote! {
-        #[async_trait :: async_trait]
+        #[#async_trait::async_trait(?Send)]
         trait EventHandler {
             async fn on(&self, e: Event) -> () {
                 match (e) {
@@ -570,9 +1981,9 @@ fn test_async_mock() {
             async fn on_one_tuple(&self, arg: String) -> ();
         }
         #[cfg (test)]
-        mockall::mock! {
+        #mockall::mock! {
             MockHandler { }
-            #[async_trait :: async_trait]
+            #[#async_trait::async_trait(?Send)]
             impl EventHandler for MockHandler {
                 async fn on_one_tuple(&self, arg: String) -> ();
             }
@@ -0,0 +1,48 @@
+use enum_handler::{EnumHandler, ParseKindError};
+
+#[derive(Debug, PartialEq, EnumHandler)]
+#[enum_handler(accessors = true, kind = "CounterEventKind")]
+pub enum CounterEvent {
+    Increment,
+    Set(i32),
+    Move { from: i32, to: i32 },
+}
+
+#[test]
+fn test_accessors() {
+    let set = CounterEvent::Set(5);
+    assert!(set.is_set());
+    assert!(!set.is_increment());
+    assert_eq!(set.as_set(), Some(&5));
+    assert_eq!(set.as_move(), None);
+    assert_eq!(set.variant_name(), "Set");
+
+    let moved = CounterEvent::Move {
+        from: 1,
+        to: 2,
+    };
+    assert_eq!(moved.as_move(), Some((&1, &2)));
+    assert_eq!(moved.into_move(), Some((1, 2)));
+    assert_eq!(CounterEvent::Increment.into_set(), None);
+}
+
+#[test]
+fn test_kind() {
+    assert_eq!(
+        CounterEventKind::ALL,
+        [CounterEventKind::Increment, CounterEventKind::Set, CounterEventKind::Move]
+    );
+    assert_eq!(CounterEvent::Set(1).kind(), CounterEventKind::Set);
+    assert_eq!(
+        CounterEventKind::from(&CounterEvent::Increment),
+        CounterEventKind::Increment
+    );
+    assert_eq!(CounterEventKind::Move.to_string(), "Move");
+    assert_eq!("Set".parse::<CounterEventKind>(), Ok(CounterEventKind::Set));
+    assert_eq!(
+        "Reset".parse::<CounterEventKind>(),
+        Err(ParseKindError {
+            kind: "CounterEventKind"
+        })
+    );
+}
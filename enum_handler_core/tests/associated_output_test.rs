@@ -0,0 +1,107 @@
+use enum_handler::EnumHandler;
+
+#[derive(EnumHandler)]
+#[enum_handler(associated_output = true, mock_name = "Render", mock_output = "String")]
+pub enum ViewEvent {
+    Title(String),
+    Count(usize),
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(associated_output = true, output_bounds = "Send", is_async = true, default_implementation = true)]
+pub enum AsyncViewEvent {
+    Title(String),
+    Count(usize),
+}
+
+struct TextRenderer;
+
+impl ViewEventHandler for TextRenderer {
+    type Output = String;
+
+    fn on_title(
+        &self,
+        arg: String,
+    ) -> String {
+        format!("# {}", arg)
+    }
+
+    fn on_count(
+        &self,
+        arg: usize,
+    ) -> String {
+        arg.to_string()
+    }
+}
+
+struct LengthRenderer;
+
+impl ViewEventHandler for LengthRenderer {
+    type Output = usize;
+
+    fn on_title(
+        &self,
+        arg: String,
+    ) -> usize {
+        arg.len()
+    }
+
+    fn on_count(
+        &self,
+        arg: usize,
+    ) -> usize {
+        arg
+    }
+}
+
+struct AsyncRenderer;
+
+#[async_trait::async_trait]
+impl AsyncViewEventHandler for AsyncRenderer {
+    type Output = Option<usize>;
+
+    async fn on_count(
+        &self,
+        arg: usize,
+    ) -> Option<usize> {
+        Some(arg)
+    }
+}
+
+#[test]
+fn test_associated_output() {
+    assert_eq!(
+        TextRenderer.on(ViewEvent::Title("Counter".to_string())),
+        "# Counter"
+    );
+    assert_eq!(
+        LengthRenderer.on(ViewEvent::Title("Counter".to_string())),
+        7
+    );
+    assert_eq!(LengthRenderer.on(ViewEvent::Count(3)), 3);
+}
+
+#[tokio::test]
+async fn test_associated_output_default_implementation() {
+    assert_eq!(
+        AsyncRenderer
+            .on(AsyncViewEvent::Count(3))
+            .await,
+        Some(3)
+    );
+    assert_eq!(
+        AsyncRenderer
+            .on(AsyncViewEvent::Title("Counter".to_string()))
+            .await,
+        None
+    );
+}
+
+#[test]
+fn test_associated_output_mock() {
+    let mut mock = MockRender::new();
+    mock.expect_on_count()
+        .times(1)
+        .returning(|count| format!("{} items", count));
+    assert_eq!(mock.on(ViewEvent::Count(3)), "3 items");
+}
@@ -0,0 +1,45 @@
+use std::cell::Cell;
+
+use enum_handler::enum_handler;
+
+#[enum_handler(return_type = "i32", pass_args_by_ref = true)]
+#[derive(Debug, Clone)]
+enum CounterEvent {
+    Increment,
+    Set(i32),
+}
+
+#[derive(Default)]
+struct Counter {
+    value: Cell<i32>,
+}
+
+impl CounterEventHandler for Counter {
+    fn on_increment(&self) -> i32 {
+        self.value
+            .set(
+                self.value
+                    .get()
+                    + 1,
+            );
+        self.value
+            .get()
+    }
+
+    fn on_set(
+        &self,
+        arg: &i32,
+    ) -> i32 {
+        self.value
+            .set(*arg);
+        self.value
+            .get()
+    }
+}
+
+#[test]
+fn test_attribute_macro() {
+    let counter = Counter::default();
+    assert_eq!(counter.on(&CounterEvent::Set(41)), 41);
+    assert_eq!(counter.on(&CounterEvent::Increment.clone()), 42);
+}
@@ -0,0 +1,254 @@
+use std::{
+    rc::Rc,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc,
+    },
+};
+
+use enum_handler::EnumHandler;
+
+#[derive(EnumHandler)]
+#[enum_handler(return_type = "i32", default_return_value = "-1", pass_args_by_ref = true, blanket_impls = true)]
+pub enum CounterEvent {
+    Increment,
+    Set(i32),
+    Rename { inner: String },
+}
+
+#[derive(Default)]
+struct Counter {
+    value: AtomicI32,
+}
+
+impl CounterEventHandler for Counter {
+    fn on_increment(&self) -> i32 {
+        self.value
+            .fetch_add(1, Ordering::SeqCst)
+            + 1
+    }
+
+    fn on_set(
+        &self,
+        arg: &i32,
+    ) -> i32 {
+        self.value
+            .store(*arg, Ordering::SeqCst);
+        *arg
+    }
+
+    fn on_rename(
+        &self,
+        inner: &str,
+    ) -> i32 {
+        inner.len() as i32
+    }
+}
+
+fn dispatch(
+    handler: impl CounterEventHandler,
+    event: &CounterEvent,
+) -> i32 {
+    handler.on(event)
+}
+
+#[test]
+fn test_pointers() {
+    let mut counter = Counter::default();
+    assert_eq!(dispatch(&counter, &CounterEvent::Set(40)), 40);
+    assert_eq!(dispatch(&mut counter, &CounterEvent::Increment), 41);
+    assert_eq!(
+        dispatch(Box::new(Counter::default()), &CounterEvent::Increment),
+        1
+    );
+    assert_eq!(
+        dispatch(Rc::new(Counter::default()), &CounterEvent::Set(2)),
+        2
+    );
+
+    let shared = Arc::new(Counter::default());
+    dispatch(shared.clone(), &CounterEvent::Set(7));
+    assert_eq!(dispatch(shared, &CounterEvent::Increment), 8);
+}
+
+#[test]
+fn test_dyn_pointers() {
+    let handler: Box<dyn CounterEventHandler> = Box::new(Counter::default());
+    assert_eq!(dispatch(handler, &CounterEvent::Set(3)), 3);
+    let handler: Arc<dyn CounterEventHandler> = Arc::new(Counter::default());
+    assert_eq!(dispatch(&handler, &CounterEvent::Increment), 1);
+}
+
+#[test]
+fn test_option() {
+    let handler = Some(Counter::default());
+    assert_eq!(
+        dispatch(
+            &handler,
+            &CounterEvent::Rename {
+                inner: "abc".to_string()
+            }
+        ),
+        3
+    );
+    assert_eq!(dispatch(None::<Counter>, &CounterEvent::Increment), -1);
+}
+
+mod async_trait_handler {
+    use super::*;
+
+    #[derive(EnumHandler)]
+    #[enum_handler(is_async = true, return_type = "i32", default_return_value = "0", pass_args_by_ref = true, blanket_impls = true)]
+    pub enum StoreEvent {
+        Load(i32),
+    }
+
+    struct Store;
+
+    #[async_trait::async_trait]
+    impl StoreEventHandler for Store {
+        async fn on_load(
+            &self,
+            arg: &i32,
+        ) -> i32 {
+            *arg * 2
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_trait_pointers() {
+        let handler: Arc<dyn StoreEventHandler + Send + Sync> = Arc::new(Store);
+        assert_eq!(
+            handler
+                .on(&StoreEvent::Load(2))
+                .await,
+            4
+        );
+        assert_eq!(
+            None::<Store>
+                .on(&StoreEvent::Load(2))
+                .await,
+            0
+        );
+    }
+}
+
+mod boxed_handler {
+    use std::{future::Future, pin::Pin};
+
+    use super::*;
+
+    #[derive(EnumHandler)]
+    #[enum_handler(is_async = true, async_style = "boxed", error_type = "std::fmt::Error", pass_args_by_ref = true, blanket_impls = true)]
+    pub enum JobEvent {
+        Run { id: u32 },
+    }
+
+    struct Jobs;
+
+    impl JobEventHandler for Jobs {
+        fn on_run<'a>(
+            &'a self,
+            id: &'a u32,
+        ) -> Pin<Box<dyn Future<Output = Result<(), std::fmt::Error>> + Send + 'a>> {
+            Box::pin(async move {
+                if *id == 0 {
+                    Ok(())
+                } else {
+                    Err(std::fmt::Error)
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_boxed_pointers() {
+        let handler: Box<dyn JobEventHandler> = Box::new(Jobs);
+        assert_eq!(
+            JobEventHandler::on(
+                &handler,
+                &JobEvent::Run {
+                    id: 1
+                }
+            )
+            .await,
+            Err(std::fmt::Error)
+        );
+        assert_eq!(
+            None::<Jobs>
+                .on(&JobEvent::Run {
+                    id: 0
+                })
+                .await,
+            Ok(())
+        );
+    }
+}
+
+mod flavours_handler {
+    use super::*;
+
+    #[derive(EnumHandler)]
+    #[enum_handler(flavours = "both", associated_output = true, pass_args_by_ref = true, blanket_impls = true)]
+    pub enum PingEvent {
+        Ping,
+    }
+
+    struct Pinger;
+
+    impl PingEventHandler for Pinger {
+        type Output = &'static str;
+
+        fn on_ping(&self) -> &'static str {
+            "pong"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_flavour_of_pointers() {
+        let handler = Arc::new(Pinger);
+        assert_eq!(PingEventHandler::on(&handler, &PingEvent::Ping), "pong");
+        assert_eq!(
+            AsyncPingEventHandler::on(&handler, &PingEvent::Ping).await,
+            "pong"
+        );
+        assert_eq!(PingEventHandler::on(&None::<Pinger>, &PingEvent::Ping), "");
+    }
+}
+
+mod no_default_handler {
+    use super::*;
+
+    pub struct Rejected;
+
+    // `Result<(), Rejected>` is not `Default`, so `Option<T>` is left out without a `default_return_value`
+    #[derive(EnumHandler)]
+    #[enum_handler(return_type = "Result<(), Rejected>", blanket_impls = true)]
+    pub enum OrderEvent {
+        Place(u32),
+    }
+
+    struct Orders;
+
+    impl OrderEventHandler for Orders {
+        fn on_place(
+            &self,
+            arg: u32,
+        ) -> Result<(), Rejected> {
+            if arg > 0 {
+                Ok(())
+            } else {
+                Err(Rejected)
+            }
+        }
+    }
+
+    #[test]
+    fn test_pointers_without_default_value() {
+        assert!(Arc::new(Orders)
+            .on(OrderEvent::Place(1))
+            .is_ok());
+        let handler: Box<dyn OrderEventHandler> = Box::new(Orders);
+        assert!(OrderEventHandler::on(&handler, OrderEvent::Place(0)).is_err());
+    }
+}
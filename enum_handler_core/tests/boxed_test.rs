@@ -0,0 +1,115 @@
+use std::sync::atomic::{AtomicI32, Ordering};
+
+use enum_handler::EnumHandler;
+
+#[derive(EnumHandler)]
+#[enum_handler(is_async = true, async_style = "boxed", return_type = "i32", pass_args_by_ref = true, mock_name = "BoxedCounter")]
+pub enum CounterEvent {
+    Increment,
+    Set(i32),
+    Rename { name: String },
+}
+
+#[derive(Default)]
+struct Counter {
+    value: AtomicI32,
+}
+
+impl CounterEventHandler for Counter {
+    fn on_increment<'a>(&'a self) -> std::pin::Pin<Box<dyn std::future::Future<Output = i32> + Send + 'a>> {
+        Box::pin(async move {
+            self.value
+                .fetch_add(1, Ordering::SeqCst)
+                + 1
+        })
+    }
+
+    fn on_set<'a>(
+        &'a self,
+        arg: &'a i32,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = i32> + Send + 'a>> {
+        Box::pin(async move {
+            self.value
+                .store(*arg, Ordering::SeqCst);
+            *arg
+        })
+    }
+
+    fn on_rename<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = i32> + Send + 'a>> {
+        Box::pin(async move { name.len() as i32 })
+    }
+}
+
+#[tokio::test]
+async fn test_boxed_dyn_handler() {
+    let handler: Box<dyn CounterEventHandler> = Box::new(Counter::default());
+    assert_eq!(
+        handler
+            .on(&CounterEvent::Set(41))
+            .await,
+        41
+    );
+    assert_eq!(
+        handler
+            .on(&CounterEvent::Increment)
+            .await,
+        42
+    );
+    assert_eq!(
+        handler
+            .on(&CounterEvent::Rename {
+                name: "counter".to_string()
+            })
+            .await,
+        7
+    );
+}
+
+#[tokio::test]
+async fn test_boxed_mock() {
+    let mut mock = MockBoxedCounter::new();
+    mock.expect_on_rename()
+        .times(1)
+        .withf(|name| name == "counter")
+        .returning(|_| 7);
+    let handler: Box<dyn CounterEventHandler> = Box::new(mock);
+    assert_eq!(
+        handler
+            .on(&CounterEvent::Rename {
+                name: "counter".to_string()
+            })
+            .await,
+        7
+    );
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(flavours = "both", async_style = "boxed", return_type = "i32")]
+pub enum ResetEvent {
+    Reset(i32),
+}
+
+struct Reset;
+
+impl ResetEventHandler for Reset {
+    fn on_reset(
+        &self,
+        arg: i32,
+    ) -> i32 {
+        arg
+    }
+}
+
+#[test]
+fn test_boxed_flavours_both() {
+    let handler: Box<dyn AsyncResetEventHandler> = Box::new(Reset);
+    assert_eq!(
+        enum_handler::block_on(handler.on(ResetEvent::Reset(42))),
+        42
+    );
+    let blocking = BlockingResetEventHandler(Reset);
+    assert_eq!(ResetEventHandler::on(&blocking, ResetEvent::Reset(7)), 7);
+}
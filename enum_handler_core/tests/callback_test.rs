@@ -0,0 +1,43 @@
+use enum_handler::EnumHandler;
+
+/// Generates a table with the handler method and the fields of every variant.
+macro_rules! routes {
+    (enum = $enum:ident, trait = $trait:ident, variants = [$(($variant:ident, $handler:ident, [$($field:ident: $ty:ty),*])),*]) => {
+        impl $enum {
+            pub const ROUTES: &'static [(&'static str, &'static str, &'static [(&'static str, &'static str)])] = &[
+                $((stringify!($variant), stringify!($handler), &[$((stringify!($field), stringify!($ty))),*])),*
+            ];
+        }
+    };
+}
+
+#[derive(Debug, EnumHandler)]
+#[enum_handler(callback = routes)]
+pub enum Command {
+    Open { path: String },
+    Move(i32, i32),
+    Quit,
+}
+
+#[derive(Debug, EnumHandler)]
+#[enum_handler(callback = routes, flavours = "both")]
+pub enum Query {
+    Find { id: u32 },
+}
+
+#[test]
+fn test_callback_once_for_both_flavours() {
+    assert_eq!(Query::ROUTES, &[("Find", "on_find", &[("id", "u32")][..])]);
+}
+
+#[test]
+fn test_callback() {
+    assert_eq!(
+        Command::ROUTES,
+        &[
+            ("Open", "on_open", &[("path", "String")][..]),
+            ("Move", "on_move", &[("arg0", "i32"), ("arg1", "i32")][..]),
+            ("Quit", "on_quit", &[][..]),
+        ]
+    );
+}
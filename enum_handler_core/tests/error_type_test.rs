@@ -0,0 +1,159 @@
+use std::{error::Error, fmt};
+
+use enum_handler::EnumHandler;
+
+#[derive(Debug, PartialEq)]
+pub struct CounterError(String);
+
+impl fmt::Display for CounterError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "counter error: {}", self.0)
+    }
+}
+
+impl Error for CounterError {}
+
+impl From<std::num::ParseIntError> for CounterError {
+    fn from(e: std::num::ParseIntError) -> Self {
+        CounterError(e.to_string())
+    }
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(error_type = "CounterError", return_type = "i32", default_return_value = "0", default_implementation = true)]
+pub enum CounterEvent {
+    Increment,
+    Parse(String),
+    #[enum_handler(returns = "i64")]
+    Widen(i32),
+    #[enum_handler(returns = "String")]
+    Describe,
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(error_type = "CounterError", is_async = true, default_implementation = true)]
+pub enum AsyncCounterEvent {
+    Fail,
+    Succeed,
+}
+
+struct Counter;
+
+struct AsyncCounter;
+
+impl CounterEventHandler for Counter {
+    fn on_parse(
+        &self,
+        arg: String,
+    ) -> Result<i32, CounterError> {
+        Ok(arg.parse::<i32>()?)
+    }
+
+    fn on_widen(
+        &self,
+        arg: i32,
+    ) -> Result<i64, CounterError> {
+        Ok(i64::from(arg))
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncCounterEventHandler for AsyncCounter {
+    async fn on_fail(&self) -> Result<(), CounterError> {
+        Err(CounterError("failed".to_string()))
+    }
+}
+
+#[test]
+fn test_on_returns_handler_result() {
+    let counter = Counter;
+    assert!(matches!(
+        counter.on(CounterEvent::Increment),
+        Ok(CounterEventOutput::Increment(0))
+    ));
+    assert!(matches!(
+        counter.on(CounterEvent::Parse("42".to_string())),
+        Ok(CounterEventOutput::Parse(42))
+    ));
+    assert!(matches!(
+        counter.on(CounterEvent::Widen(-1)),
+        Ok(CounterEventOutput::Widen(-1))
+    ));
+    assert!(counter
+        .on(CounterEvent::Parse("x".to_string()))
+        .is_err());
+    assert!(matches!(
+        counter.on(CounterEvent::Describe),
+        Ok(CounterEventOutput::Describe(description)) if description.is_empty()
+    ));
+}
+
+#[test]
+fn test_try_on_wraps_error_with_variant() {
+    let counter = Counter;
+    let error = counter
+        .try_on(CounterEvent::Parse("x".to_string()))
+        .err()
+        .unwrap();
+    assert_eq!(error.variant, "Parse");
+    assert_eq!(error.to_string(), "the handler of Parse failed");
+    assert_eq!(
+        error
+            .source()
+            .unwrap()
+            .to_string(),
+        "counter error: invalid digit found in string"
+    );
+}
+
+#[tokio::test]
+async fn test_async_try_on() {
+    let counter = AsyncCounter;
+    assert!(counter
+        .try_on(AsyncCounterEvent::Succeed)
+        .await
+        .is_ok());
+    let error = counter
+        .try_on(AsyncCounterEvent::Fail)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(error.variant, "Fail");
+    assert_eq!(error.source, CounterError("failed".to_string()));
+}
+
+mod string_error {
+    use super::*;
+
+    #[derive(EnumHandler)]
+    #[enum_handler(error_type = "String")]
+    pub enum ParseEvent {
+        Parse(String),
+    }
+
+    struct Parser;
+
+    impl ParseEventHandler for Parser {
+        fn on_parse(
+            &self,
+            arg: String,
+        ) -> Result<(), String> {
+            arg.parse::<i32>()
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+    }
+
+    #[test]
+    fn test_string_error_type() {
+        let error = Parser
+            .try_on(ParseEvent::Parse("x".to_string()))
+            .unwrap_err();
+        assert_eq!(error.variant, "Parse");
+        assert_eq!(error.source, "invalid digit found in string");
+        assert_eq!(error.to_string(), "the handler of Parse failed");
+    }
+}
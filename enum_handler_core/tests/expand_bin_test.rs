@@ -0,0 +1,75 @@
+use std::{
+    env, fs,
+    process::{self, Command},
+};
+
+const SOURCE: &str = r#"
+use enum_handler::EnumHandler;
+
+#[derive(Debug, EnumHandler)]
+pub enum CounterEvent {
+    Increment,
+}
+
+mod store {
+    #[enum_handler::enum_handler(mock_name = "StoreMock")]
+    pub enum StoreEvent {
+        Clear,
+    }
+}
+
+pub enum Ignored {
+    A,
+}
+"#;
+
+fn expand(args: &[&str]) -> (i32, String) {
+    let path = env::temp_dir().join(format!(
+        "enum_handler_expand_{}_{}.rs",
+        process::id(),
+        args.len()
+    ));
+    fs::write(&path, SOURCE).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_enum-handler-expand"))
+        .args(args)
+        .arg(&path)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+    (
+        output
+            .status
+            .code()
+            .unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn test_expand_all_enums() {
+    let (code, stdout) = expand(&[]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("pub trait CounterEventHandler {"));
+    assert!(stdout.contains("pub trait StoreEventHandler {"));
+    assert!(stdout.contains("mockall::mock!"));
+    assert!(!stdout.contains("IgnoredHandler"));
+}
+
+#[test]
+fn test_expand_json_with_enum_filter() {
+    let (code, stdout) = expand(&["--json", "--enum", "StoreEvent"]);
+    assert_eq!(code, 0);
+    let output: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(
+        output
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(output[0]["enum"], "StoreEvent");
+    assert!(output[0]["code"]
+        .as_str()
+        .unwrap()
+        .contains("pub enum StoreEvent {"));
+}
@@ -0,0 +1,118 @@
+use std::sync::atomic::{AtomicI32, Ordering};
+
+use enum_handler::EnumHandler;
+
+#[derive(EnumHandler)]
+#[enum_handler(flavours = "both", return_type = "i32")]
+pub enum CounterEvent {
+    Increment,
+    Set(i32),
+}
+
+#[derive(Default)]
+struct SyncCounter {
+    value: AtomicI32,
+}
+
+impl CounterEventHandler for SyncCounter {
+    fn on_increment(&self) -> i32 {
+        self.value
+            .fetch_add(1, Ordering::SeqCst)
+            + 1
+    }
+
+    fn on_set(
+        &self,
+        arg: i32,
+    ) -> i32 {
+        self.value
+            .store(arg, Ordering::SeqCst);
+        arg
+    }
+}
+
+#[derive(Default)]
+struct AsyncCounter {
+    value: AtomicI32,
+}
+
+#[async_trait::async_trait]
+impl AsyncCounterEventHandler for AsyncCounter {
+    async fn on_increment(&self) -> i32 {
+        self.value
+            .fetch_add(1, Ordering::SeqCst)
+            + 1
+    }
+
+    async fn on_set(
+        &self,
+        arg: i32,
+    ) -> i32 {
+        self.value
+            .store(arg, Ordering::SeqCst);
+        arg
+    }
+}
+
+async fn dispatch(
+    handler: &(dyn AsyncCounterEventHandler + Sync),
+    event: CounterEvent,
+) -> i32 {
+    handler
+        .on(event)
+        .await
+}
+
+#[tokio::test]
+async fn test_sync_handler_as_async_handler() {
+    let counter = SyncCounter::default();
+    assert_eq!(dispatch(&counter, CounterEvent::Set(41)).await, 41);
+    assert_eq!(dispatch(&counter, CounterEvent::Increment).await, 42);
+}
+
+#[test]
+fn test_blocking_adapter() {
+    let counter = BlockingCounterEventHandler(AsyncCounter::default());
+    assert_eq!(CounterEventHandler::on(&counter, CounterEvent::Set(41)), 41);
+    assert_eq!(
+        CounterEventHandler::on(&counter, CounterEvent::Increment),
+        42
+    );
+}
+
+mod default_implementation {
+    use super::*;
+
+    #[derive(EnumHandler)]
+    #[enum_handler(flavours = "both", return_type = "i32", default_return_value = "-1", default_implementation = true)]
+    pub enum QueryEvent {
+        Count,
+        Find(i32),
+    }
+
+    struct AsyncQuery;
+
+    #[async_trait::async_trait]
+    impl AsyncQueryEventHandler for AsyncQuery {
+        async fn on_count(&self) -> i32 {
+            3
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_implementation_of_both_flavours() {
+        struct SyncQuery;
+        impl QueryEventHandler for SyncQuery {}
+        assert_eq!(
+            AsyncQueryEventHandler::on(&SyncQuery, QueryEvent::Find(1)).await,
+            -1
+        );
+    }
+
+    #[test]
+    fn test_blocking_adapter_with_default_implementation() {
+        let query = BlockingQueryEventHandler(AsyncQuery);
+        assert_eq!(QueryEventHandler::on(&query, QueryEvent::Count), 3);
+        assert_eq!(QueryEventHandler::on(&query, QueryEvent::Find(1)), -1);
+    }
+}
@@ -0,0 +1,75 @@
+use std::{env, fs, process};
+
+use enum_handler_core::{enum_handler_args::EnumHandlerArgs, Generator};
+use syn::parse_quote;
+
+const SOURCE: &str = r#"
+use enum_handler::EnumHandler;
+
+#[derive(Debug, Clone, EnumHandler)]
+#[enum_handler(trait_name = "CounterHandler")]
+pub enum CounterEvent {
+    Increment,
+}
+
+pub enum Ignored {
+    A,
+}
+"#;
+
+#[test]
+fn test_generate_item_enum_with_args() {
+    let output = Generator::new()
+        .args(EnumHandlerArgs {
+            trait_name: "StoreHandler".to_string(),
+            is_async: true,
+            no_async_trait_macro: true,
+            ..Default::default()
+        })
+        .item_enum(parse_quote! {
+            pub enum StoreEvent {
+                Clear,
+                Set(u32),
+            }
+        })
+        .generate()
+        .unwrap()
+        .to_string();
+    assert!(output.contains("pub trait StoreHandler"));
+    assert!(output.contains("async fn on_set"));
+    assert!(!output.contains("enum StoreEvent"));
+}
+
+#[test]
+fn test_write_to_with_enums() {
+    let dir = env::temp_dir().join(format!("enum_handler_generator_{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let source_path = dir.join("events.rs");
+    fs::write(&source_path, SOURCE).unwrap();
+
+    let path = Generator::new()
+        .enum_source(&source_path)
+        .include_enums(true)
+        .file_name("handlers.rs")
+        .write_to(&dir)
+        .unwrap();
+    let code = fs::read_to_string(&path).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(path, dir.join("handlers.rs"));
+    assert!(code.contains("#[derive(Debug, Clone)]\npub enum CounterEvent {"));
+    assert!(code.contains("pub trait CounterHandler {"));
+    assert!(!code.contains("#[enum_handler"));
+    assert!(!code.contains("Ignored"));
+}
+
+#[test]
+fn test_missing_enum_source() {
+    let result = Generator::new()
+        .enum_source("does/not/exist.rs")
+        .generate();
+    assert!(matches!(
+        result,
+        Err(enum_handler_core::Error::CannotReadFile(_))
+    ));
+}
@@ -0,0 +1,201 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use enum_handler::EnumHandler;
+
+#[derive(EnumHandler)]
+#[enum_handler(return_type = "i32", pass_args_by_ref = true, lock_impls = true)]
+pub enum CounterEvent {
+    Increment,
+    Set(i32),
+}
+
+#[derive(Default)]
+struct Counter {
+    value: i32,
+}
+
+impl CounterEventHandlerMut for Counter {
+    fn on_increment(&mut self) -> i32 {
+        self.value += 1;
+        self.value
+    }
+
+    fn on_set(
+        &mut self,
+        arg: &i32,
+    ) -> i32 {
+        self.value = *arg;
+        self.value
+    }
+}
+
+#[test]
+fn test_mutex() {
+    let counter = Mutex::new(Counter::default());
+    assert_eq!(counter.on(&CounterEvent::Set(41)), 41);
+    assert_eq!(counter.on(&CounterEvent::Increment), 42);
+    assert_eq!(
+        counter
+            .into_inner()
+            .unwrap()
+            .value,
+        42
+    );
+}
+
+#[test]
+fn test_rw_lock() {
+    let counter = RwLock::new(Counter::default());
+    assert_eq!(counter.on(&CounterEvent::Increment), 1);
+    let mut counter = counter
+        .into_inner()
+        .unwrap();
+    assert_eq!(counter.on(&CounterEvent::Increment), 2);
+}
+
+#[test]
+fn test_poisoned_mutex() {
+    let counter = Arc::new(Mutex::new(Counter::default()));
+    let poisoner = counter.clone();
+    std::thread::spawn(move || {
+        let _guard = poisoner
+            .lock()
+            .unwrap();
+        panic!("poison the lock");
+    })
+    .join()
+    .unwrap_err();
+    assert_eq!(counter.on(&CounterEvent::Increment), 1);
+}
+
+mod async_mutex {
+    use super::*;
+
+    #[derive(EnumHandler)]
+    #[enum_handler(is_async = true, async_mutex = tokio::sync::Mutex, lock_impls = true)]
+    pub enum StoreEvent {
+        Push(String),
+    }
+
+    #[derive(Default)]
+    struct Store {
+        items: Vec<String>,
+    }
+
+    #[async_trait::async_trait]
+    impl StoreEventHandlerMut for Store {
+        async fn on_push(
+            &mut self,
+            arg: String,
+        ) {
+            self.items
+                .push(arg);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_mutex() {
+        let store = Arc::new(tokio::sync::Mutex::new(Store::default()));
+        let tasks = (0..4)
+            .map(|i| {
+                let store = store.clone();
+                tokio::spawn(async move {
+                    store
+                        .on(StoreEvent::Push(i.to_string()))
+                        .await
+                })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            task.await
+                .unwrap();
+        }
+        assert_eq!(
+            store
+                .lock()
+                .await
+                .items
+                .len(),
+            4
+        );
+    }
+}
+
+mod boxed {
+    use std::{future::Future, pin::Pin};
+
+    use super::*;
+
+    #[derive(EnumHandler)]
+    #[enum_handler(is_async = true, async_style = "boxed", async_mutex = "tokio::sync::Mutex", lock_impls = true)]
+    pub enum JobEvent {
+        Run { id: u32 },
+    }
+
+    #[derive(Default)]
+    struct Jobs {
+        runs: u32,
+    }
+
+    impl JobEventHandlerMut for Jobs {
+        fn on_run<'a>(
+            &'a mut self,
+            id: u32,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+            Box::pin(async move { self.runs += id })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_boxed_async_mutex() {
+        let jobs = tokio::sync::Mutex::new(Jobs::default());
+        jobs.on(JobEvent::Run {
+            id: 2,
+        })
+        .await;
+        jobs.on(JobEvent::Run {
+            id: 3,
+        })
+        .await;
+        assert_eq!(
+            jobs.into_inner()
+                .runs,
+            5
+        );
+    }
+}
+
+mod flavours {
+    use super::*;
+
+    #[derive(EnumHandler)]
+    #[enum_handler(flavours = "both", lock_impls = true)]
+    pub enum PingEvent {
+        Ping,
+    }
+
+    #[derive(Default)]
+    struct Pinger {
+        pings: u32,
+    }
+
+    impl PingEventHandlerMut for Pinger {
+        fn on_ping(&mut self) {
+            self.pings += 1;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_flavour_of_mutex() {
+        let pinger = Mutex::new(Pinger::default());
+        PingEventHandler::on(&pinger, PingEvent::Ping);
+        AsyncPingEventHandler::on(&pinger, PingEvent::Ping).await;
+        assert_eq!(
+            pinger
+                .into_inner()
+                .unwrap()
+                .pings,
+            2
+        );
+    }
+}
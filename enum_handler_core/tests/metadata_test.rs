@@ -0,0 +1,50 @@
+// every #[enum_handler(...)] attribute describes its own trait, options may repeat
+#![allow(clippy::duplicated_attributes)]
+
+use enum_handler::{EnumHandler, EnumHandlerMeta, HandlerFieldInfo};
+
+#[derive(EnumHandler)]
+#[enum_handler(metadata = true, default_implementation = true)]
+#[enum_handler(trait_name = "CounterEventLogger", handler_name = "log", metadata = true)]
+pub enum CounterEvent {
+    Increment,
+    Set(i32),
+    Move(std::option::Option<i32>, &'static str),
+}
+
+#[test]
+fn test_handler_methods() {
+    let methods = CounterEvent::HANDLER_METHODS;
+    assert_eq!(methods.len(), 6);
+
+    let set = &methods[1];
+    assert_eq!(set.trait_name, "CounterEventHandler");
+    assert_eq!(set.variant, "Set");
+    assert_eq!(set.method, "on_set");
+    assert_eq!(
+        set.fields,
+        &[HandlerFieldInfo {
+            name: "arg",
+            ty: "i32"
+        }]
+    );
+    assert!(set.has_default);
+
+    let log_move = &methods[5];
+    assert_eq!(log_move.trait_name, "CounterEventLogger");
+    assert_eq!(log_move.method, "log_move");
+    assert_eq!(
+        log_move.fields,
+        &[
+            HandlerFieldInfo {
+                name: "arg0",
+                ty: "std::option::Option<i32>",
+            },
+            HandlerFieldInfo {
+                name: "arg1",
+                ty: "&'static str",
+            },
+        ]
+    );
+    assert!(!log_move.has_default);
+}
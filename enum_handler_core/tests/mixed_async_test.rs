@@ -0,0 +1,100 @@
+use std::sync::Mutex;
+
+use enum_handler::EnumHandler;
+
+#[derive(EnumHandler)]
+#[enum_handler(return_type = "usize", mock_name = "MixedStore")]
+pub enum StoreEvent {
+    Clear,
+    #[enum_handler(async)]
+    Save(String),
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(is_async = true, async_style = "boxed", return_type = "usize", trait_name = "BoxedStoreEventHandler")]
+pub enum BoxedStoreEvent {
+    #[enum_handler(sync)]
+    Clear,
+    Save(String),
+}
+
+#[derive(Default)]
+struct Store {
+    saved: Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl StoreEventHandler for Store {
+    fn on_clear(&self) -> usize {
+        let mut saved = self
+            .saved
+            .lock()
+            .unwrap();
+        saved.clear();
+        0
+    }
+
+    async fn on_save(
+        &self,
+        arg: String,
+    ) -> usize {
+        let mut saved = self
+            .saved
+            .lock()
+            .unwrap();
+        saved.push(arg);
+        saved.len()
+    }
+}
+
+impl BoxedStoreEventHandler for Store {
+    fn on_clear(&self) -> usize {
+        StoreEventHandler::on_clear(self)
+    }
+
+    fn on_save<'a>(
+        &'a self,
+        arg: String,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = usize> + Send + 'a>> {
+        StoreEventHandler::on_save(self, arg)
+    }
+}
+
+#[tokio::test]
+async fn test_mixed_handler() {
+    let store = Store::default();
+    assert_eq!(
+        StoreEventHandler::on(&store, StoreEvent::Save("a".to_string())).await,
+        1
+    );
+    assert_eq!(
+        BoxedStoreEventHandler::on(&store, BoxedStoreEvent::Save("b".to_string())).await,
+        2
+    );
+    assert_eq!(StoreEventHandler::on_clear(&store), 0);
+    assert_eq!(
+        BoxedStoreEventHandler::on(&store, BoxedStoreEvent::Clear).await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_mixed_mock() {
+    let mut mock = MockMixedStore::new();
+    mock.expect_on_clear()
+        .times(1)
+        .returning(|| 0);
+    mock.expect_on_save()
+        .times(1)
+        .returning(|_| 1);
+    assert_eq!(
+        mock.on(StoreEvent::Save("a".to_string()))
+            .await,
+        1
+    );
+    assert_eq!(
+        mock.on(StoreEvent::Clear)
+            .await,
+        0
+    );
+}
@@ -17,6 +17,24 @@ fn test_mock() {
     mock.on(&event);
 }
 
+// mockall refers to the event type from a nested module, so it can't be declared inside the test fn
+#[derive(EnumHandler, PartialEq, Debug)]
+#[enum_handler(mock_name = "PassEventTestEvent", pass_args_by_ref = true, pass_event = true)]
+enum PassEvent {
+    Hello(String),
+}
+
+#[test]
+fn test_mock_pass_event() {
+    let event = PassEvent::Hello("world".to_string());
+    let mut mock = MockPassEventTestEvent::new();
+    mock.expect_on_hello()
+        .times(1)
+        .withf(|e, s| *e == PassEvent::Hello("world".to_string()) && s == "world")
+        .returning(|_, _| ());
+    mock.on(&event);
+}
+
 #[tokio::test]
 async fn test_async_mock() {
     #[derive(EnumHandler)]
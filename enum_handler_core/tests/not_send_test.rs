@@ -0,0 +1,68 @@
+use std::{cell::RefCell, rc::Rc};
+
+use enum_handler::EnumHandler;
+
+#[derive(EnumHandler)]
+#[enum_handler(is_async = true, async_send = false, mock_name = "NotSendLog")]
+pub enum LogEvent {
+    Append(String),
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(is_async = true, async_style = "boxed", async_send = false, trait_name = "BoxedLogEventHandler")]
+pub enum BoxedLogEvent {
+    Append(String),
+}
+
+#[derive(Default)]
+struct Log {
+    lines: Rc<RefCell<Vec<String>>>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl LogEventHandler for Log {
+    async fn on_append(
+        &self,
+        arg: String,
+    ) {
+        self.lines
+            .borrow_mut()
+            .push(arg);
+    }
+}
+
+impl BoxedLogEventHandler for Log {
+    fn on_append<'a>(
+        &'a self,
+        arg: String,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + 'a>> {
+        Box::pin(async move {
+            self.lines
+                .borrow_mut()
+                .push(arg);
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_not_send_handler() {
+    let log = Log::default();
+    LogEventHandler::on(&log, LogEvent::Append("one".to_string())).await;
+    BoxedLogEventHandler::on(&log, BoxedLogEvent::Append("two".to_string())).await;
+    assert_eq!(
+        *log.lines
+            .borrow(),
+        vec!["one", "two"]
+    );
+}
+
+#[tokio::test]
+async fn test_not_send_mock() {
+    let mut mock = MockNotSendLog::new();
+    mock.expect_on_append()
+        .times(1)
+        .withf(|s| s == "one")
+        .returning(|_| ());
+    mock.on(LogEvent::Append("one".to_string()))
+        .await;
+}
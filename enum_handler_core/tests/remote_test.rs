@@ -0,0 +1,162 @@
+use std::cmp::Ordering;
+
+use enum_handler::EnumHandler;
+
+mod protocol {
+    pub enum Request {
+        Ping,
+        Echo(String),
+        Resize { width: u32, height: u32 },
+    }
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(remote = "protocol::Request", return_type = "String")]
+enum RequestMirror {
+    Ping,
+    Echo(String),
+    Resize { width: u32, height: u32 },
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(remote = "std::cmp::Ordering", return_type = "&'static str")]
+enum OrderingMirror {
+    Less,
+    Equal,
+    Greater,
+}
+
+struct Server;
+
+impl RequestMirrorHandler for Server {
+    fn on_ping(&self) -> String {
+        "pong".to_string()
+    }
+
+    fn on_echo(
+        &self,
+        arg: String,
+    ) -> String {
+        arg
+    }
+
+    fn on_resize(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> String {
+        format!("{}x{}", width, height)
+    }
+}
+
+struct Comparator;
+
+impl OrderingMirrorHandler for Comparator {
+    fn on_less(&self) -> &'static str {
+        "<"
+    }
+
+    fn on_equal(&self) -> &'static str {
+        "="
+    }
+
+    fn on_greater(&self) -> &'static str {
+        ">"
+    }
+}
+
+#[test]
+fn test_remote_enum() {
+    let server = Server;
+    assert_eq!(server.on(protocol::Request::Ping), "pong");
+    assert_eq!(
+        server.on(protocol::Request::Echo("hello".to_string())),
+        "hello"
+    );
+    assert_eq!(
+        server.on(protocol::Request::Resize {
+            width: 800,
+            height: 600
+        }),
+        "800x600"
+    );
+}
+
+#[test]
+fn test_remote_std_enum() {
+    let comparator = Comparator;
+    assert_eq!(comparator.on(1.cmp(&2)), "<");
+    assert_eq!(comparator.on(Ordering::Equal), "=");
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(
+    remote = "std::sync::atomic::Ordering",
+    non_exhaustive = true,
+    return_type = "&'static str",
+    default_implementation = true,
+    default_return_value = "\"unknown\""
+)]
+enum AtomicOrderingMirror {
+    Relaxed,
+    Release,
+    Acquire,
+    AcqRel,
+    SeqCst,
+}
+
+struct Atomics;
+
+impl AtomicOrderingMirrorHandler for Atomics {
+    fn on_seq_cst(&self) -> &'static str {
+        "sequentially consistent"
+    }
+}
+
+#[test]
+fn test_non_exhaustive_remote() {
+    use std::sync::atomic;
+
+    assert_eq!(
+        Atomics.on(atomic::Ordering::SeqCst),
+        "sequentially consistent"
+    );
+    assert_eq!(Atomics.on(atomic::Ordering::Relaxed), "unknown");
+    assert_eq!(Atomics.on_unknown(atomic::Ordering::Acquire), "unknown");
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(remote = "std::sync::atomic::Ordering", non_exhaustive = true, pass_args_by_ref = true, trait_name = "FenceHandler")]
+enum FenceMirror {
+    Relaxed,
+    Release,
+    Acquire,
+    AcqRel,
+    SeqCst,
+}
+
+struct Fences;
+
+impl FenceHandler for Fences {
+    fn on_relaxed(&self) {}
+
+    fn on_release(&self) {}
+
+    fn on_acquire(&self) {}
+
+    fn on_acq_rel(&self) {}
+
+    fn on_seq_cst(&self) {}
+
+    fn on_unknown(
+        &self,
+        e: &std::sync::atomic::Ordering,
+    ) {
+        panic!("unknown ordering {e:?}");
+    }
+}
+
+#[test]
+fn test_non_exhaustive_remote_by_ref() {
+    Fences.on(&std::sync::atomic::Ordering::AcqRel);
+}
@@ -0,0 +1,114 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use enum_handler::EnumHandler;
+
+#[derive(EnumHandler)]
+#[enum_handler(flavours = "both", no_async_trait_macro = true)]
+pub enum StoreRequest {
+    #[enum_handler(returns = "Option<i32>")]
+    Get(String),
+    Set {
+        key: String,
+        value: i32,
+    },
+    #[enum_handler(returns = "bool")]
+    Delete(String),
+}
+
+#[derive(Default)]
+struct Store {
+    values: Mutex<HashMap<String, i32>>,
+}
+
+impl StoreRequestHandler for Store {
+    fn on_get(
+        &self,
+        arg: String,
+    ) -> Option<i32> {
+        self.values
+            .lock()
+            .unwrap()
+            .get(&arg)
+            .copied()
+    }
+
+    fn on_set(
+        &self,
+        key: String,
+        value: i32,
+    ) {
+        self.values
+            .lock()
+            .unwrap()
+            .insert(key, value);
+    }
+
+    fn on_delete(
+        &self,
+        arg: String,
+    ) -> bool {
+        self.values
+            .lock()
+            .unwrap()
+            .remove(&arg)
+            .is_some()
+    }
+}
+
+#[test]
+fn test_variant_returns() {
+    let store = Store::default();
+    let set = StoreRequest::Set {
+        key: "a".to_string(),
+        value: 1,
+    };
+    assert!(matches!(
+        StoreRequestHandler::on(&store, set),
+        StoreRequestOutput::Set(())
+    ));
+    assert!(matches!(
+        StoreRequestHandler::on(&store, StoreRequest::Get("a".to_string())),
+        StoreRequestOutput::Get(Some(1))
+    ));
+    assert!(matches!(
+        StoreRequestHandler::on(&store, StoreRequest::Delete("a".to_string())),
+        StoreRequestOutput::Delete(true)
+    ));
+    assert!(matches!(
+        StoreRequestHandler::on(&store, StoreRequest::Get("a".to_string())),
+        StoreRequestOutput::Get(None)
+    ));
+}
+
+#[tokio::test]
+async fn test_async_variant_returns() {
+    let store = Store::default();
+    assert!(matches!(
+        AsyncStoreRequestHandler::on(&store, StoreRequest::Delete("a".to_string())).await,
+        StoreRequestOutput::Delete(false)
+    ));
+}
+
+#[derive(EnumHandler)]
+#[enum_handler(default_implementation = true)]
+pub enum PingRequest {
+    Ping,
+    #[enum_handler(returns = "u32")]
+    Count,
+}
+
+struct Pinger;
+
+impl PingRequestHandler for Pinger {}
+
+#[test]
+fn test_default_implementation_of_variant_returns() {
+    assert!(matches!(
+        Pinger.on(PingRequest::Ping),
+        PingRequestOutput::Ping(())
+    ));
+    assert!(matches!(
+        Pinger.on(PingRequest::Count),
+        PingRequestOutput::Count(0)
+    ));
+}
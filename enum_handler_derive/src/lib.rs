@@ -1,9 +1,9 @@
 #![doc = include_str!("../README.md")]
 
-use enum_handler_core::enum_handler_core;
+use enum_handler_core::{enum_handler_attribute_core, enum_handler_core};
 
 #[proc_macro_derive(EnumHandler, attributes(enum_handler))]
-pub fn enum_handler(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+pub fn enum_handler_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input2 = proc_macro2::TokenStream::from(input.clone());
     let result = enum_handler_core(input2);
     match result {
@@ -11,3 +11,17 @@ pub fn enum_handler(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         Err(e) => panic!("{}", e),
     }
 }
+
+#[proc_macro_attribute]
+pub fn enum_handler(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let attr2 = proc_macro2::TokenStream::from(attr);
+    let item2 = proc_macro2::TokenStream::from(item);
+    let result = enum_handler_attribute_core(attr2, item2);
+    match result {
+        Ok(result) => proc_macro::TokenStream::from(result),
+        Err(e) => panic!("{}", e),
+    }
+}
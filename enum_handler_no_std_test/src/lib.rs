@@ -0,0 +1,113 @@
+//! The generated code must only use `core` (and `alloc` for boxed futures), so it builds in `no_std` crates.
+#![no_std]
+// the default implementations don't use the fields
+#![allow(unused_variables)]
+
+extern crate alloc;
+
+#[cfg(test)]
+extern crate std;
+
+use enum_handler::EnumHandler;
+
+#[derive(Debug, PartialEq, EnumHandler)]
+#[enum_handler(return_type = "u32", accessors = true, kind = "CommandKind", metadata = true)]
+#[enum_handler(trait_name = "FallibleCommandHandler", pass_args_by_ref = true, error_type = "SensorError")]
+#[enum_handler(trait_name = "BoxedCommandHandler", is_async = true, async_style = "boxed", async_send = false, default_implementation = true)]
+pub enum Command {
+    Reset,
+    Set(u32),
+    Blink { times: u8, delay_ms: u16 },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SensorError;
+
+impl core::fmt::Display for SensorError {
+    fn fmt(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        f.write_str("sensor error")
+    }
+}
+
+impl core::error::Error for SensorError {}
+
+#[derive(EnumHandler)]
+#[enum_handler(remote = "core::cmp::Ordering", return_type = "i8", default_implementation = true, default_return_value = "0")]
+pub enum Ordering {
+    Less,
+    Equal,
+    Greater,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Led;
+
+    impl CommandHandler for Led {
+        fn on_reset(&self) -> u32 {
+            0
+        }
+
+        fn on_set(
+            &self,
+            arg: u32,
+        ) -> u32 {
+            arg
+        }
+
+        fn on_blink(
+            &self,
+            times: u8,
+            _delay_ms: u16,
+        ) -> u32 {
+            times.into()
+        }
+    }
+
+    impl FallibleCommandHandler for Led {
+        fn on_reset(&self) -> Result<(), SensorError> {
+            Err(SensorError)
+        }
+
+        fn on_set(
+            &self,
+            _arg: &u32,
+        ) -> Result<(), SensorError> {
+            Ok(())
+        }
+
+        fn on_blink(
+            &self,
+            _times: &u8,
+            _delay_ms: &u16,
+        ) -> Result<(), SensorError> {
+            Ok(())
+        }
+    }
+
+    impl BoxedCommandHandler for Led {}
+
+    impl OrderingHandler for Led {
+        fn on_less(&self) -> i8 {
+            -1
+        }
+    }
+
+    #[test]
+    fn test_no_std_handlers() {
+        assert_eq!(CommandHandler::on(&Led, Command::Set(3)), 3);
+        assert_eq!(CommandHandler::on(&Led, Command::Reset), 0);
+        assert!(FallibleCommandHandler::try_on(&Led, &Command::Reset).is_err());
+        assert_eq!(Command::Set(1).kind(), CommandKind::Set);
+        assert_eq!("Blink".parse::<CommandKind>(), Ok(CommandKind::Blink));
+        assert!(Command::Reset.is_reset());
+        assert_eq!(OrderingHandler::on(&Led, core::cmp::Ordering::Less), -1);
+        // the boxed future only needs `alloc`
+        let _future = BoxedCommandHandler::on(&Led, Command::Reset);
+    }
+}
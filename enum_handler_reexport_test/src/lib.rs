@@ -0,0 +1,2 @@
+//! The generated code must build with the re-exports of `enum_handler` only, run the tests with
+//! `cargo test -p enum_handler_reexport_test --features async-trait,mock`.
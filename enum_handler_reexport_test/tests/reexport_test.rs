@@ -0,0 +1,28 @@
+#![cfg(all(feature = "async-trait", feature = "mock"))]
+
+use enum_handler::{block_on, EnumHandler};
+
+#[derive(EnumHandler)]
+#[enum_handler(is_async = true, return_type = "u32", default_implementation = true, default_return_value = "1", mock_name = "StoreMock")]
+pub enum StoreEvent {
+    Load(u32),
+    Clear,
+}
+
+struct Store;
+
+impl StoreEventHandler for Store {}
+
+#[test]
+fn test_async_trait_reexport() {
+    assert_eq!(block_on(Store.on(StoreEvent::Load(7))), 1);
+}
+
+#[test]
+fn test_mock_reexport() {
+    let mut mock = MockStoreMock::new();
+    mock.expect_on_load()
+        .withf(|arg| *arg == 7)
+        .return_const(7u32);
+    assert_eq!(block_on(mock.on(StoreEvent::Load(7))), 7);
+}
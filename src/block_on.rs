@@ -0,0 +1,35 @@
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0
+            .unpark();
+    }
+}
+
+/// Runs the future to completion on the current thread.
+///
+/// This is used by the generated blocking adapters (`flavours = "both"`) to call an async handler from sync code.
+/// It does not drive any runtime, so futures that depend on a runtime (eg. tokio timers or IO) must not be used with it.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future
+            .as_mut()
+            .poll(&mut cx)
+        {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use core::{error::Error, fmt};
+
+/// The error of `FromStr` for the kind enums generated with `kind = "..."`, the given string is no variant name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKindError {
+    /// The name of the kind enum.
+    pub kind: &'static str,
+}
+
+impl fmt::Display for ParseKindError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "invalid {}", self.kind)
+    }
+}
+
+impl Error for ParseKindError {}
@@ -1,3 +1,35 @@
 #![doc = include_str!("../README.md")]
+#![no_std]
 
-pub use enum_handler_derive::EnumHandler;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "std")]
+mod block_on;
+mod kind;
+mod meta;
+
+#[cfg(feature = "std")]
+pub use block_on::block_on;
+pub use enum_handler_derive::{enum_handler, EnumHandler};
+pub use kind::ParseKindError;
+pub use meta::{EnumHandlerMeta, HandlerFieldInfo, HandlerMethodInfo};
+
+/// Used by the generated code, not a public API.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+    #[cfg(feature = "alloc")]
+    pub use alloc::rc::Rc;
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    pub use alloc::sync::Arc;
+    #[cfg(feature = "async-trait")]
+    pub use async_trait;
+    #[cfg(feature = "mock")]
+    pub use mockall;
+    #[cfg(feature = "std")]
+    pub use std::sync::{Mutex, PoisonError, RwLock};
+}
@@ -0,0 +1,24 @@
+/// Describes the handler methods of the traits generated with `metadata = true`, implemented for the enum.
+pub trait EnumHandlerMeta {
+    /// One entry for each variant and trait, in declaration order.
+    const HANDLER_METHODS: &'static [HandlerMethodInfo];
+}
+
+/// A handler method of a generated trait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandlerMethodInfo {
+    pub trait_name: &'static str,
+    pub variant: &'static str,
+    pub method: &'static str,
+    pub fields: &'static [HandlerFieldInfo],
+    /// The trait has a default implementation of the method (`default_implementation = true`).
+    pub has_default: bool,
+}
+
+/// A field of a variant, passed to the handler method as the parameter `name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandlerFieldInfo {
+    pub name: &'static str,
+    /// The type as written in the enum.
+    pub ty: &'static str,
+}